@@ -1,112 +1,91 @@
 use super::{
-  arithmetic_codes::{CODE_1_2, CODE_1_4, CODE_3_4, CODE_MAX},
+  arithmetic_codes::PRECISION,
   frequency::{FreqTable, SymbolFreq},
-  ArithmeticCompressor,
+  interval::IntervalEncoder,
+  ArithmeticCompressor, ArithmeticError,
 };
 use crate::{FromBits, Transform};
-use std::convert::Infallible;
+use std::{collections::VecDeque, marker::PhantomData};
 
-/// Arithmetic Encoding algorithm
-pub struct ArithmeticEncoder {
-  frequencies: FreqTable,
-  range: (u32, u32),
-  pending_num: usize,
-  pending_bit: Option<bool>,
+/// Arithmetic Encoding algorithm, with `P` bit code values
+pub struct ArithmeticEncoder<F = FreqTable, C = u8, const P: u32 = PRECISION> {
+  frequencies: F,
+  interval: IntervalEncoder<P>,
+  /// Bits describing the model, output before any coded bits
+  header: VecDeque<bool>,
+  adaptive: bool,
+  ended: bool,
+  failed: bool,
+  symbol: PhantomData<C>,
 }
 
-impl From<ArithmeticCompressor> for ArithmeticEncoder {
-  fn from(value: ArithmeticCompressor) -> Self {
+impl<F, C, const P: u32> From<ArithmeticCompressor<F, C, P>> for ArithmeticEncoder<F, C, P> {
+  fn from(value: ArithmeticCompressor<F, C, P>) -> Self {
+    let mut header = Vec::new();
+    if let Some((write_header, _)) = value.header {
+      write_header(&value.frequencies, &mut header);
+    }
+
     Self {
       frequencies: value.frequencies,
-      range: (0, CODE_MAX),
-      pending_num: 0,
-      pending_bit: None,
+      interval: IntervalEncoder::default(),
+      header: header.into(),
+      adaptive: value.adaptive,
+      ended: false,
+      failed: false,
+      symbol: PhantomData,
     }
   }
 }
 
-impl From<ArithmeticCompressor> for (ArithmeticEncoder, FromBits<u8>) {
-  fn from(value: ArithmeticCompressor) -> Self {
+impl<F, C, const P: u32> From<ArithmeticCompressor<F, C, P>>
+  for (ArithmeticEncoder<F, C, P>, FromBits<u8>)
+{
+  fn from(value: ArithmeticCompressor<F, C, P>) -> Self {
     (value.into(), Default::default())
   }
 }
 
-impl ArithmeticEncoder {
-  /// Returns any bits still to be output,<br>
-  /// resulting from fixing convergence on 0.5.
-  fn pending_output(&mut self) -> Option<bool> {
-    if self.pending_num == 0 {
-      self.pending_bit = None;
+impl<F: SymbolFreq<C>, C: Copy, const P: u32> Transform<C> for ArithmeticEncoder<F, C, P> {
+  type Out = Result<bool, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = C>) -> Option<Self::Out> {
+    if self.failed {
+      return None;
     }
-    let bit = self.pending_bit?;
-    self.pending_num -= 1;
-    Some(bit)
-  }
-
-  /// Shifts `self.range = (low, high)` left one bit,<br>
-  /// filling `low` with `0` and `high` with `1`.<br>
-  /// Masks off low and high with `MAX_CODE`.
-  fn advance(&mut self) {
-    let (mut low, mut high) = self.range;
-    (low, high) = (low << 1, (high << 1) | 1);
-    (low, high) = (low & CODE_MAX, high & CODE_MAX);
-    self.range = (low, high);
-  }
-}
-
-impl Transform<u8> for ArithmeticEncoder {
-  type Out = Result<bool, Infallible>;
-  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
-    // if we have pending bits to output, output them
-    if let Some(bit) = self.pending_output() {
+    if let Some(bit) = self.header.pop_front() {
       return Some(Ok(bit));
     }
 
-    // both high and low are in one half:
-    // expand range, add pending bits and output bit
-    let (low, high) = self.range;
-    if high < CODE_1_2 {
-      self.advance();
-      self.pending_bit = Some(true);
-      return Some(Ok(false));
-    }
-    if low >= CODE_1_2 {
-      self.advance();
-      self.pending_bit = Some(false);
-      return Some(Ok(true));
-    }
+    loop {
+      // output bits until the range is wide enough for the next item
+      if let Some(bit) = self.interval.poll() {
+        return Some(Ok(bit));
+      }
 
-    // low and high are likely to converge around 0.5:
-    // increase the number of pending bits to output
-    // and expand the range outwards
-    if (low >= CODE_1_4) & (high < CODE_3_4) {
-      self.pending_num += 1;
-      self.range = (low & !CODE_1_4, high | CODE_1_4);
-      self.advance();
-    }
-    // at this point, low < 0.25 && 0.75 <= high
-    // therefore we can start decoding the next item
-    else {
-      // get next character or output pending bits
-      let some_c = iter.next();
-      let Some(c) = some_c else {
-        return self.pending_output().map(Ok);
-      };
+      if self.ended {
+        return self.interval.flush().map(Ok);
+      }
 
-      // decode frequencies for the next character
-      let (l, h) = self.frequencies.range(c);
-      let len = self.frequencies.len();
+      // get next character or mark the end of the stream,
+      // so the decoder knows exactly where to stop
+      let next = iter.next();
+      let range = match next {
+        Some(c) => self.frequencies.range(c),
+        None => self.frequencies.end(),
+      };
 
       // update range based on these frequencies
-      let width = high - low + 1;
-      self.range = (
-        low + (width * l) / len, //
-        low + (width * h) / len - 1,
-      );
-    }
+      if let Err(e) = self.interval.encode(range, self.frequencies.len()) {
+        self.failed = true;
+        return Some(Err(e));
+      }
 
-    // we won't need to worry too much about this call
-    // as it's basically the poster child for tail call optimisation
-    self.next(iter)
+      match next {
+        // the decoder makes the same update after decoding `c`
+        Some(c) if self.adaptive => self.frequencies.increment(c),
+        Some(_) => {}
+        None => self.ended = true,
+      }
+    }
   }
 }
@@ -0,0 +1,30 @@
+use std::{error::Error, fmt};
+
+/// Errors produced when arithmetic coding fails
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ArithmeticError {
+  /// The model gave an item an empty frequency range, so it can't be encoded
+  ZeroFrequency,
+  /// The coded bits ran out before the end of stream marker was decoded
+  Truncated,
+  /// The coded bits pointed to a value the model has no symbol for
+  OutOfRange,
+  /// The model's frequencies are empty, or sum to more than the coder allows
+  InvalidModel,
+  /// The bits ahead of the coded bits don't describe a valid model
+  InvalidHeader,
+}
+
+impl fmt::Display for ArithmeticError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::ZeroFrequency => write!(f, "item has zero frequency in the model"),
+      Self::Truncated => write!(f, "coded stream ended before the end marker"),
+      Self::OutOfRange => write!(f, "coded value matches no symbol in the model"),
+      Self::InvalidModel => write!(f, "model frequencies can't be coded"),
+      Self::InvalidHeader => write!(f, "header doesn't describe a valid model"),
+    }
+  }
+}
+
+impl Error for ArithmeticError {}
@@ -0,0 +1,229 @@
+use super::{
+  arithmetic_codes::{freq_max, Code, FREQ_MAX, MAX_PRECISION},
+  Symbol, SymbolFreq,
+};
+
+/// Symbol frequencies stored in a Fenwick (binary indexed) tree.<br>
+/// Like `FreqTable`, but with `O(log n)` ranges, lookups and increments,<br>
+/// so it stays fast for adaptive models over large alphabets.
+///
+/// The end of stream marker always has a frequency of one,<br>
+/// sitting just above the symbol frequencies.
+#[derive(Clone)]
+pub struct FenwickTable {
+  /// `tree[i]` holds the sum of the `i & (!i + 1)` counts ending at `i - 1`
+  tree: Vec<Code>,
+  total: Code,
+  /// The factor counts are divided by when the table fills up
+  aging: Code,
+  /// The largest `len` the table can grow to
+  limit: Code,
+}
+
+impl Default for FenwickTable {
+  fn default() -> Self {
+    Self::new(256)
+  }
+}
+
+impl FenwickTable {
+  /// A table over `size` symbols, each starting with a frequency of one.
+  ///
+  /// ## Panics
+  ///
+  /// If `size` is zero or more than `FREQ_MAX / 2`,<br>
+  /// as then there's no room left for symbols to adapt.
+  pub fn new(size: usize) -> Self {
+    Self::with_size_and_limit(size, FREQ_MAX)
+  }
+
+  /// A table over `size` symbols that can grow to a total of `limit`,<br>
+  /// for alphabets too large for `FREQ_MAX`. Like `with_limit`, coding<br>
+  /// needs a precision with at least that `freq_max`.
+  ///
+  /// ## Panics
+  ///
+  /// If `size` is zero or more than `limit / 2`,<br>
+  /// or `limit` is beyond what any precision can code.
+  pub fn with_size_and_limit(size: usize, limit: Code) -> Self {
+    assert!(size > 0, "alphabet must be non-empty");
+    Self::check_limit(size, limit);
+    Self {
+      limit,
+      ..Self::from_counts(&vec![1; size])
+    }
+  }
+
+  /// Checks `size` symbols leave room to adapt under `limit`, and it can be coded
+  fn check_limit(size: usize, limit: Code) {
+    assert!(
+      size as Code <= limit / 2,
+      "alphabet of {size} symbols is too large for a limit of {limit}"
+    );
+    assert!(
+      limit <= freq_max(MAX_PRECISION),
+      "limit of {limit} is too large for any precision"
+    );
+  }
+
+  /// Builds the tree from each symbol's count in `O(n)`
+  fn from_counts(counts: &[Code]) -> Self {
+    let mut tree = vec![0; counts.len() + 1];
+    for (i, &count) in counts.iter().enumerate() {
+      let i = i + 1;
+      tree[i] += count;
+      let parent = i + (i & i.wrapping_neg());
+      if parent < tree.len() {
+        tree[parent] += tree[i];
+      }
+    }
+
+    Self {
+      tree,
+      total: counts.iter().sum(),
+      aging: 2,
+      limit: FREQ_MAX,
+    }
+  }
+
+  /// Sets the factor counts are divided by when `increment` fills the table.<br>
+  /// Larger factors forget old statistics faster, `2` halves every count.
+  pub fn with_aging(self, aging: Code) -> Self {
+    assert!(aging > 1, "aging factor must be at least 2");
+    Self { aging, ..self }
+  }
+
+  /// Sets the largest total frequency the table can grow to, `FREQ_MAX` by default.<br>
+  /// Higher limits let adaptive models get more skewed before rescaling,<br>
+  /// but need a coder precision with at least that `freq_max`.
+  ///
+  /// ## Panics
+  ///
+  /// If `limit` is less than twice the alphabet size,<br>
+  /// or is beyond what any precision can code.
+  pub fn with_limit(self, limit: Code) -> Self {
+    Self::check_limit(self.size(), limit);
+    Self { limit, ..self }
+  }
+
+  /// The number of symbols in the table
+  pub fn size(&self) -> usize {
+    self.tree.len() - 1
+  }
+
+  /// The sum of the frequencies of symbols before `index`
+  fn prefix(&self, mut index: usize) -> Code {
+    let mut sum = 0;
+    while index > 0 {
+      sum += self.tree[index];
+      index &= index - 1;
+    }
+    sum
+  }
+
+  /// Adds `value` to the frequency of the symbol at `index`
+  fn add(&mut self, index: usize, value: Code) {
+    let mut i = index + 1;
+    while i < self.tree.len() {
+      self.tree[i] += value;
+      i += i & i.wrapping_neg();
+    }
+    self.total += value;
+  }
+
+  /// The frequency range for the symbol at `index`,<br>
+  /// empty if `index` is outside of the alphabet.
+  pub fn range_of(&self, index: usize) -> (Code, Code) {
+    if index >= self.size() {
+      return (0, 0);
+    }
+    let low = self.prefix(index);
+    let (mut high, mut i) = (low + self.tree[index + 1], index + 1);
+    // `tree[index + 1]` covers a block ending at `index`, so remove
+    // the counts in that block that come before `index`
+    let stop = i & (i - 1);
+    i -= 1;
+    while i > stop {
+      high -= self.tree[i];
+      i &= i - 1;
+    }
+    (low, high)
+  }
+
+  /// Searches for the index of the symbol with a frequency range containing `i`
+  pub fn lookup_index(&self, i: Code) -> Option<((Code, Code), usize)> {
+    if i >= self.total {
+      return None;
+    }
+
+    // descend the tree, skipping over blocks whose counts sum to at most `i`
+    let (mut pos, mut remaining) = (0, i);
+    let mut step = (self.tree.len() - 1).next_power_of_two();
+    while step > 0 {
+      if let Some(&count) = self.tree.get(pos + step) {
+        if count <= remaining {
+          pos += step;
+          remaining -= count;
+        }
+      }
+      step >>= 1;
+    }
+    Some((self.range_of(pos), pos))
+  }
+
+  /// Bumps the frequency of the symbol at `index` by one.<br>
+  /// When the table is full, every count is first divided by the aging factor.
+  pub fn increment_index(&mut self, index: usize) {
+    if index >= self.size() {
+      return;
+    }
+    if self.total + 1 >= self.limit {
+      self.rescale(self.aging);
+    }
+    self.add(index, 1);
+  }
+
+  /// Divides every symbol's frequency by `divisor`, rounding up<br>
+  /// so that no symbol is left with a frequency of zero.
+  pub fn rescale(&mut self, divisor: Code) {
+    let counts: Vec<_> = (0..self.size())
+      .map(|index| {
+        let (low, high) = self.range_of(index);
+        (high - low).div_ceil(divisor)
+      })
+      .collect();
+    *self = Self {
+      aging: self.aging,
+      limit: self.limit,
+      ..Self::from_counts(&counts)
+    };
+  }
+}
+
+impl<C: Symbol> SymbolFreq<C> for FenwickTable {
+  #[inline]
+  fn len(&self) -> Code {
+    self.total + 1
+  }
+
+  #[inline]
+  fn range(&self, item: C) -> (Code, Code) {
+    self.range_of(item.index())
+  }
+
+  #[inline]
+  fn lookup(&self, i: Code) -> Option<((Code, Code), C)> {
+    let (range, index) = self.lookup_index(i)?;
+    Some((range, C::from_index(index)?))
+  }
+
+  #[inline]
+  fn end(&self) -> (Code, Code) {
+    (self.total, self.total + 1)
+  }
+
+  #[inline]
+  fn increment(&mut self, item: C) {
+    self.increment_index(item.index())
+  }
+}
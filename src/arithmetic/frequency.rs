@@ -1,6 +1,12 @@
-use super::arithmetic_codes::{Code, FREQ_MAX};
+use super::arithmetic_codes::{freq_max, Code, FREQ_MAX, MAX_PRECISION};
 use std::array;
 
+/// A probability model over symbols of type `C`.
+///
+/// Implementations must keep the frequency range of every symbol<br>
+/// that may be coded non-empty, and `len` at most the coder's `freq_max`.<br>
+/// The range returned by `end` mustn't overlap any symbol's range.
+#[allow(clippy::len_without_is_empty)]
 pub trait SymbolFreq<C> {
   /// The sum of frequencies in the table
   fn len(&self) -> Code;
@@ -8,35 +14,53 @@ pub trait SymbolFreq<C> {
   fn range(&self, item: C) -> (Code, Code);
   /// Searches for the item with a frequency range that contains `i`
   fn lookup(&self, i: Code) -> Option<((Code, Code), C)>;
+  /// The frequency range of the marker coded after the last item
+  fn end(&self) -> (Code, Code);
+  /// Bumps the frequency of `item` after it has been coded.<br>
+  /// Only called by adaptive coders, in the same order when encoding and decoding.
+  fn increment(&mut self, item: C);
 }
 
+/// A table of cumulative symbol frequencies over bytes.<br>
+/// The end of stream marker always has a frequency of one,<br>
+/// sitting just above the byte frequencies.
 #[derive(Clone, Copy)]
-pub struct FreqTable([Code; 256]);
+pub struct FreqTable {
+  cumulative: [Code; 256],
+  /// The factor counts are divided by when the table fills up
+  aging: Code,
+  /// The largest `len` the table can grow to
+  limit: Code,
+}
 
 impl Default for FreqTable {
   fn default() -> Self {
-    Self(array::from_fn(|i| i as Code + 1))
+    Self {
+      cumulative: array::from_fn(|i| i as Code + 1),
+      aging: 2,
+      limit: FREQ_MAX,
+    }
   }
 }
 
 impl SymbolFreq<u8> for FreqTable {
   #[inline]
   fn len(&self) -> Code {
-    self.0[255]
+    self.cumulative[255] + 1
   }
 
   #[inline]
   fn range(&self, item: u8) -> (Code, Code) {
     if item == 0 {
-      return (0, self.0[0]);
+      return (0, self.cumulative[0]);
     }
     let item = item as usize;
-    (self.0[item - 1], self.0[item])
+    (self.cumulative[item - 1], self.cumulative[item])
   }
 
   #[inline]
   fn lookup(&self, i: Code) -> Option<((Code, Code), u8)> {
-    let pos = self.0.partition_point(|v| v <= &i);
+    let pos = self.cumulative.partition_point(|v| v <= &i);
     if pos == 256 {
       None
     } else {
@@ -44,21 +68,101 @@ impl SymbolFreq<u8> for FreqTable {
       Some((self.range(pos), pos))
     }
   }
+
+  #[inline]
+  fn end(&self) -> (Code, Code) {
+    (self.cumulative[255], self.cumulative[255] + 1)
+  }
+
+  /// Bumps the frequency of `item` by one.<br>
+  /// When the table is full, every count is first divided by the aging factor.
+  fn increment(&mut self, item: u8) {
+    while self.add(item, 1).is_none() {
+      self.rescale(self.aging);
+    }
+  }
 }
 
 impl FreqTable {
-  /// Adds `value` to the frequency of a single `item`
+  /// A table with the given frequency for each byte.<br>
+  /// Bytes with a count of zero can't be coded, and coders need<br>
+  /// the sum below their `freq_max` to leave room for the end marker.
+  ///
+  /// ## Panics
+  ///
+  /// If the counts and the end marker sum past `Code::MAX`.
+  pub fn from_counts(counts: [Code; 256]) -> Self {
+    let mut sum: Code = 0;
+    Self {
+      cumulative: counts.map(|count| {
+        sum = (sum.checked_add(count))
+          .filter(|sum| *sum < Code::MAX)
+          .expect("counts must leave room for the end marker");
+        sum
+      }),
+      ..Default::default()
+    }
+  }
+
+  /// A table matching the byte histogram of `items`, scaled down to fit<br>
+  /// within `FREQ_MAX`. Bytes missing from `items` get a frequency of one.
+  pub fn from_sample(items: impl IntoIterator<Item = u8>) -> Self {
+    let mut histogram: [Code; 256] = [0; 256];
+    for item in items {
+      histogram[item as usize] += 1;
+    }
+
+    // leave room for one count per byte, plus the end marker
+    let space = FREQ_MAX - 257;
+    let total: Code = histogram.iter().sum();
+    let scale = |count: Code| match total > space {
+      true => count * space / total,
+      false => count,
+    };
+    Self::from_counts(histogram.map(|count| scale(count).max(1)))
+  }
+
+  /// Sets the factor counts are divided by when `increment` fills the table.<br>
+  /// Larger factors forget old statistics faster, `2` halves every count.
+  pub fn with_aging(self, aging: Code) -> Self {
+    assert!(aging > 1, "aging factor must be at least 2");
+    Self { aging, ..self }
+  }
+
+  /// Sets the largest total frequency the table can grow to, `FREQ_MAX` by default.<br>
+  /// Higher limits let adaptive models get more skewed before rescaling,<br>
+  /// but need a coder precision with at least that `freq_max`.
+  ///
+  /// ## Panics
+  ///
+  /// If `limit` leaves no room for symbols to adapt,<br>
+  /// or is beyond what any precision can code.
+  pub fn with_limit(self, limit: Code) -> Self {
+    assert!(
+      limit > 257,
+      "limit must leave room for every byte and the end marker"
+    );
+    assert!(
+      limit <= freq_max(MAX_PRECISION),
+      "limit of {limit} is too large for any precision"
+    );
+    Self { limit, ..self }
+  }
+
+  /// Adds `value` to the frequency of a single `item`,<br>
+  /// returning `None` if the total would exceed the table's limit.
   pub fn add(&mut self, item: u8, value: Code) -> Option<()> {
-    if self.0[255] == FREQ_MAX {
+    if value > self.limit.saturating_sub(self.len()) {
       return None;
     }
     for j in (item as usize)..256 {
-      self.0[j] += value;
+      self.cumulative[j] += value;
     }
     Some(())
   }
 
-  /// Adds all the frequencies in `updates` to `self`
+  /// Adds all the frequencies in `updates` to `self`,<br>
+  /// returning `None` if the total would exceed the table's limit.
   pub fn update(&mut self, mut updates: [Code; 256]) -> Option<()> {
     // accumulate updates prior to adding
     let mut sum = 0;
@@ -67,12 +171,56 @@ impl FreqTable {
       *update = sum;
     }
 
-    // equal to `updates[255] + self.0[255] < FREQ_MAX` but won't
-    // overflow / underflow as `self.0[255] < FREQ_MAX` by invariants
-    (updates[255] < FREQ_MAX - self.0[255]).then(|| {
-      for (freq, update) in self.0.iter_mut().zip(updates) {
+    // equal to `updates[255] + self.len() <= self.limit` but won't overflow
+    (updates[255] <= self.limit.saturating_sub(self.len())).then(|| {
+      for (freq, update) in self.cumulative.iter_mut().zip(updates) {
         *freq += update
       }
     })
   }
+
+  /// The byte frequencies, ignoring the end marker, scaled to sum to `1 << bits`.<br>
+  /// Bytes keep a non-zero frequency if they had one, and an empty<br>
+  /// table quantizes as if uniform. `bits` must be at least 8.
+  pub(crate) fn quantized(&self, bits: u32) -> [Code; 256] {
+    let mut counts: [Code; 256] = array::from_fn(|i| {
+      let (low, high) = self.range(i as u8);
+      high - low
+    });
+    if counts.iter().all(|count| *count == 0) {
+      counts = [1; 256];
+    }
+    let total: Code = counts.iter().sum();
+
+    let mut freqs = counts.map(|count| (count << bits).div_ceil(total));
+
+    // fix rounding by taking from, or giving to, the most frequent byte
+    let target = 1 << bits;
+    let mut sum: Code = freqs.iter().sum();
+    while sum != target {
+      let Some(largest) = (0..256).max_by_key(|&i| freqs[i]) else {
+        unreachable!("there are 256 bytes")
+      };
+      if sum > target {
+        freqs[largest] -= 1;
+        sum -= 1;
+      } else {
+        freqs[largest] += 1;
+        sum += 1;
+      }
+    }
+    freqs
+  }
+
+  /// Divides every symbol's frequency by `divisor`, rounding up<br>
+  /// so that no symbol is left with a frequency of zero.
+  pub fn rescale(&mut self, divisor: Code) {
+    let (mut prev, mut sum) = (0, 0);
+    for freq in &mut self.cumulative {
+      let count = *freq - prev;
+      prev = *freq;
+      sum += count.div_ceil(divisor);
+      *freq = sum;
+    }
+  }
 }
@@ -0,0 +1,86 @@
+use super::{
+  arithmetic_codes::{freq_max, Code, FREQ_MAX, MAX_PRECISION},
+  FreqTable, SymbolFreq,
+};
+use std::iter;
+
+/// Models that can be written ahead of the coded bits,<br>
+/// so that decoders can rebuild them from the stream alone.
+pub trait FreqHeader: Sized {
+  /// Appends a description of the model's frequencies to `bits`
+  fn write_header(&self, bits: &mut Vec<bool>);
+  /// Rebuilds a model from the description written by `write_header`,<br>
+  /// returning `None` if the bits don't describe a valid model.
+  fn read_header(bits: &mut dyn Iterator<Item = bool>) -> Option<Self>;
+}
+
+/// `FreqHeader::write_header` for a model `F`
+pub(crate) type WriteHeader<F> = fn(&F, &mut Vec<bool>);
+/// `FreqHeader::read_header` for a model `F`
+pub(crate) type ReadHeader<F> = fn(&mut dyn Iterator<Item = bool>) -> Option<F>;
+
+/// Writes `value > 0` as an Elias gamma code: the number of bits<br>
+/// after the leading one in unary, then the value's bits themselves.
+pub(crate) fn write_gamma(value: Code, bits: &mut Vec<bool>) {
+  let width = Code::BITS - value.leading_zeros();
+  bits.extend((1..width).map(|_| false));
+  bits.extend((0..width).rev().map(|i| (value >> i) & 1 == 1));
+}
+
+/// Reads a value written by `write_gamma`, if it fits in a `Code`
+pub(crate) fn read_gamma(bits: &mut dyn Iterator<Item = bool>) -> Option<Code> {
+  let mut width = 1;
+  while !bits.next()? {
+    width += 1;
+    if width > Code::BITS {
+      return None;
+    }
+  }
+
+  let mut value: Code = 1;
+  for _ in 1..width {
+    value = (value << 1) | Code::from(bits.next()?);
+  }
+  Some(value)
+}
+
+/// Reads a value written by `write_gamma`, failing with `truncated`<br>
+/// if the bits run out, or `invalid` if it doesn't fit in a `Code`.
+pub(crate) fn try_read_gamma<E>(
+  bits: &mut dyn Iterator<Item = bool>,
+  truncated: E,
+  invalid: E,
+) -> Result<Code, E> {
+  let mut ended = false;
+  let value = read_gamma(&mut iter::from_fn(|| {
+    let bit = bits.next();
+    ended |= bit.is_none();
+    bit
+  }));
+  value.ok_or(match ended {
+    true => truncated,
+    false => invalid,
+  })
+}
+
+/// Each byte's frequency plus one as an Elias gamma code,<br>
+/// so bytes that never occur cost a single bit.
+impl FreqHeader for FreqTable {
+  fn write_header(&self, bits: &mut Vec<bool>) {
+    for item in 0..=255 {
+      let (low, high) = self.range(item);
+      write_gamma(high - low + 1, bits);
+    }
+  }
+
+  fn read_header(bits: &mut dyn Iterator<Item = bool>) -> Option<Self> {
+    let mut counts = [0; 256];
+    let mut total: Code = 1;
+    for count in &mut counts {
+      *count = read_gamma(bits)? - 1;
+      total = (total.checked_add(*count)).filter(|t| *t <= freq_max(MAX_PRECISION))?;
+    }
+    // the coder itself rejects tables too large for its precision
+    Some(Self::from_counts(counts).with_limit(total.max(FREQ_MAX)))
+  }
+}
@@ -0,0 +1,245 @@
+use super::{
+  arithmetic_codes::{Code, Codes, PRECISION},
+  ArithmeticError,
+};
+
+/// The interval narrowing half of arithmetic encoding.<br>
+/// This knows nothing about models, it's handed frequency ranges<br>
+/// by the encoders built on top of it and produces the coded bits.
+pub(crate) struct IntervalEncoder<const P: u32 = PRECISION> {
+  range: (Code, Code),
+  pending_num: usize,
+  pending_bit: Option<bool>,
+  flushed: bool,
+}
+
+impl<const P: u32> Default for IntervalEncoder<P> {
+  fn default() -> Self {
+    Self {
+      range: (0, Codes::<P>::CODE_MAX),
+      pending_num: 0,
+      pending_bit: None,
+      flushed: false,
+    }
+  }
+}
+
+impl<const P: u32> IntervalEncoder<P> {
+  /// Returns any bits still to be output,<br>
+  /// resulting from fixing convergence on 0.5.
+  fn pending_output(&mut self) -> Option<bool> {
+    if self.pending_num == 0 {
+      self.pending_bit = None;
+    }
+    let bit = self.pending_bit?;
+    self.pending_num -= 1;
+    Some(bit)
+  }
+
+  /// Shifts `self.range = (low, high)` left one bit,<br>
+  /// filling `low` with `0` and `high` with `1`.<br>
+  /// Masks off low and high with `MAX_CODE`.
+  fn advance(&mut self) {
+    let (mut low, mut high) = self.range;
+    (low, high) = (low << 1, (high << 1) | 1);
+    (low, high) = (low & Codes::<P>::CODE_MAX, high & Codes::<P>::CODE_MAX);
+    self.range = (low, high);
+  }
+
+  /// Returns the next bit produced by widening the interval,<br>
+  /// or `None` once the interval is wide enough to code another symbol.
+  pub fn poll(&mut self) -> Option<bool> {
+    // if we have pending bits to output, output them
+    if let Some(bit) = self.pending_output() {
+      return Some(bit);
+    }
+
+    loop {
+      // both high and low are in one half:
+      // expand range, add pending bits and output bit
+      let (low, high) = self.range;
+      if high < Codes::<P>::CODE_1_2 {
+        self.advance();
+        self.pending_bit = Some(true);
+        return Some(false);
+      }
+      if low >= Codes::<P>::CODE_1_2 {
+        self.advance();
+        self.pending_bit = Some(false);
+        return Some(true);
+      }
+
+      // low and high are likely to converge around 0.5:
+      // increase the number of pending bits to output
+      // and expand the range outwards
+      if (low >= Codes::<P>::CODE_1_4) & (high < Codes::<P>::CODE_3_4) {
+        self.pending_num += 1;
+        self.range = (low & !Codes::<P>::CODE_1_4, high | Codes::<P>::CODE_1_4);
+        self.advance();
+        continue;
+      }
+
+      // at this point, low < 0.25 && 0.75 <= high
+      // therefore we can start encoding the next symbol
+      return None;
+    }
+  }
+
+  /// Narrows the interval to the frequency range `(l, h)` out of `len`.<br>
+  /// Should only be called once `poll` has returned `None`.
+  pub fn encode(&mut self, (l, h): (Code, Code), len: Code) -> Result<(), ArithmeticError> {
+    if l >= h {
+      return Err(ArithmeticError::ZeroFrequency);
+    }
+    if (h > len) | (len > Codes::<P>::FREQ_MAX) {
+      return Err(ArithmeticError::InvalidModel);
+    }
+
+    let (low, high) = self.range;
+    let width = high - low + 1;
+    self.range = (
+      low + (width * l) / len, //
+      low + (width * h) / len - 1,
+    );
+    Ok(())
+  }
+
+  /// Outputs the bits left over once there's nothing left to encode.<br>
+  /// These pick out a value inside the final interval, so that<br>
+  /// the decoder can still locate the last symbol encoded.
+  pub fn flush(&mut self) -> Option<bool> {
+    if self.flushed {
+      return self.pending_output();
+    }
+
+    // low < 0.25 <= 0.5 <= high or low <= 0.5 < 0.75 <= high
+    // so outputting 0.25 or 0.5 respectively lands inside the range
+    let (low, _) = self.range;
+    let bit = low >= Codes::<P>::CODE_1_4;
+    self.flushed = true;
+    self.pending_num += 1;
+    self.pending_bit = Some(!bit);
+    Some(bit)
+  }
+}
+
+/// The interval narrowing half of arithmetic decoding.<br>
+/// Mirrors `IntervalEncoder`, reading the coded bits and<br>
+/// locating symbols for the decoders built on top of it.
+pub(crate) struct IntervalDecoder<const P: u32 = PRECISION> {
+  range: (Code, Code),
+  value: Code,
+  pending: u32,
+  started: bool,
+}
+
+impl<const P: u32> Default for IntervalDecoder<P> {
+  fn default() -> Self {
+    Self {
+      range: (0, Codes::<P>::CODE_MAX),
+      value: 0,
+      pending: Codes::<P>::CODE_MAX.trailing_ones(),
+      started: false,
+    }
+  }
+}
+
+impl<const P: u32> IntervalDecoder<P> {
+  /// Fetches a bit from an iterator, marking `self` complete if it fails
+  fn next_bit(&mut self, iter: &mut impl Iterator<Item = bool>) -> Option<Code> {
+    if let Some(bit) = iter.next() {
+      return Some(bit.into());
+    }
+
+    if self.pending > 0 {
+      self.pending -= 1;
+      return Some(0);
+    }
+
+    None
+  }
+
+  /// Shifts `self.range = (low, high)` and `self.value` left by one bit.<br>
+  /// Filling `low` with `0`, `high` with `1` and `self.value` with `iter.next`.
+  fn advance(&mut self, iter: &mut impl Iterator<Item = bool>) -> Option<()> {
+    let (mut low, mut high) = self.range;
+    (low, high) = (low << 1, (high << 1) | 1);
+    self.range = (low & Codes::<P>::CODE_MAX, high & Codes::<P>::CODE_MAX);
+
+    self.value <<= 1;
+    self.value |= self.next_bit(iter)?;
+    self.value &= Codes::<P>::CODE_MAX;
+    Some(())
+  }
+
+  /// Fetches the initial data from the iterator, padding with zeros
+  fn fetch_value(&mut self, iter: &mut impl Iterator<Item = bool>) -> Code {
+    let mut value = 0;
+    for i in (0..Codes::<P>::CODE_MAX.trailing_ones()).rev() {
+      let Some(bit) = self.next_bit(iter) else {
+        break;
+      };
+      value |= bit << i;
+    }
+    value
+  }
+
+  /// Widens the interval, then scales the coded value into `0..len`.<br>
+  /// The symbol whose frequency range contains the result is the one encoded.<br>
+  /// Fails if the bits, and the zero padding after them, run out.
+  pub fn target(
+    &mut self,
+    iter: &mut impl Iterator<Item = bool>,
+    len: Code,
+  ) -> Result<Code, ArithmeticError> {
+    if (len == 0) | (len > Codes::<P>::FREQ_MAX) {
+      return Err(ArithmeticError::InvalidModel);
+    }
+    if !self.started {
+      self.started = true;
+      self.value = self.fetch_value(iter);
+    }
+
+    // move low and high until low < CODE_1_4 and CODE_3_4 <= high
+    // at which point we can decode the next character
+    loop {
+      let (low, high) = self.range;
+
+      if (Codes::<P>::CODE_1_2 <= low) | (high < Codes::<P>::CODE_1_2) {
+        self.advance(iter).ok_or(ArithmeticError::Truncated)?;
+        continue;
+      }
+
+      if (Codes::<P>::CODE_1_4 <= low) & (high < Codes::<P>::CODE_3_4) {
+        self.range = (low & !Codes::<P>::CODE_1_4, high | Codes::<P>::CODE_1_4);
+        self.value =
+          (self.value.checked_sub(Codes::<P>::CODE_1_4)).ok_or(ArithmeticError::OutOfRange)?;
+        self.advance(iter).ok_or(ArithmeticError::Truncated)?;
+        continue;
+      }
+
+      break;
+    }
+
+    // `low <= self.value <= high` holds whenever symbols are decoded from
+    // the ranges that contain their targets, but check to avoid overflow
+    let (low, high) = self.range;
+    let width = high - low + 1;
+    let offset = (self.value.checked_sub(low))
+      .filter(|offset| *offset < width)
+      .ok_or(ArithmeticError::OutOfRange)?;
+    Ok(((offset + 1) * len - 1) / width)
+  }
+
+  /// Narrows the interval to the frequency range `(l, h)` out of `len`,<br>
+  /// exactly as `IntervalEncoder::encode` did for the same symbol.<br>
+  /// The range must contain the value returned by `target`.
+  pub fn decode(&mut self, (l, h): (Code, Code), len: Code) {
+    let (low, high) = self.range;
+    let width = high - low + 1;
+    self.range = (
+      low + (width * l) / len, //
+      low + (width * h) / len - 1,
+    );
+  }
+}
@@ -1,18 +1,56 @@
 use crate::Compressor;
-use std::convert::Infallible;
+use std::marker::PhantomData;
 
 /// Generic types and constants for frequency and code values
 mod arithmetic_codes {
-  pub type Code = u32;
-  pub const CODE_MAX: Code = (1 << (Code::BITS / 2 + 1)) - 1;
-  pub const FREQ_MAX: Code = Code::MAX / (CODE_MAX + 1);
-  pub const CODE_1_2: Code = CODE_MAX / 2 + 1;
-  pub const CODE_1_4: Code = CODE_1_2 / 2;
-  pub const CODE_3_4: Code = CODE_1_4 * 3;
+  pub type Code = u64;
+  /// The default number of bits in code values
+  pub const PRECISION: u32 = 17;
+  /// The most bits code values can have, leaving 31 bits for frequencies
+  pub const MAX_PRECISION: u32 = Code::BITS / 2 + 1;
+  /// The largest total frequency that can be coded at the default precision
+  pub const FREQ_MAX: Code = freq_max(PRECISION);
+
+  /// The largest total frequency that can be coded with `precision` bit codes.<br>
+  /// Every symbol needs a non-empty range once the interval's been widened<br>
+  /// past a quarter of the codes, and `width * len` mustn't overflow.
+  pub const fn freq_max(precision: u32) -> Code {
+    let quarter = (1 << (precision - 2)) - 1;
+    let overflow = Code::MAX >> precision;
+    if quarter < overflow {
+      quarter
+    } else {
+      overflow
+    }
+  }
+
+  /// Code values with `P` bits of precision
+  pub struct Codes<const P: u32>;
+
+  impl<const P: u32> Codes<P> {
+    pub const CODE_MAX: Code = (1 << P) - 1;
+    pub const FREQ_MAX: Code = freq_max(P);
+    pub const CODE_1_2: Code = Self::CODE_MAX / 2 + 1;
+    pub const CODE_1_4: Code = Self::CODE_1_2 / 2;
+    pub const CODE_3_4: Code = Self::CODE_1_4 * 3;
+  }
 }
 
+mod error;
+pub use error::ArithmeticError;
+mod fenwick;
+pub use fenwick::FenwickTable;
 mod frequency;
+mod header;
+pub use header::FreqHeader;
+pub(crate) use header::{read_gamma, try_read_gamma, write_gamma};
+use header::{ReadHeader, WriteHeader};
+mod interval;
+mod symbol;
+pub use arithmetic_codes::{freq_max, Code, FREQ_MAX, MAX_PRECISION, PRECISION};
 pub use frequency::{FreqTable, SymbolFreq};
+pub(crate) use interval::{IntervalDecoder, IntervalEncoder};
+pub use symbol::Symbol;
 mod encoder;
 pub use encoder::ArithmeticEncoder;
 mod decoder;
@@ -21,18 +59,111 @@ pub use decoder::ArithmeticDecoder;
 /// The arithmetic coding compression algorithm.<br>
 /// This is pretty much ripped directly from the excellent mark nelson [blog](https://webcf.waybackmachine.org/web/20241217122540/https://marknelson.us/posts/2014/10/19/data-compression-with-arithmetic-coding.html),<br>
 /// specifically the unsigned int implementation.
-#[derive(Default, Clone, Copy)]
-pub struct ArithmeticCompressor {
-  frequencies: FreqTable,
+///
+/// Encoding finishes with the model's end of stream marker, so decoding<br>
+/// stops at exactly the items encoded, whatever padding follows.
+///
+/// Code values have `P` bits of precision, see `with_precision`.
+#[derive(Clone, Copy)]
+pub struct ArithmeticCompressor<F = FreqTable, C = u8, const P: u32 = PRECISION> {
+  frequencies: F,
+  adaptive: bool,
+  header: Option<(WriteHeader<F>, ReadHeader<F>)>,
+  symbol: PhantomData<C>,
+}
+
+impl Default for ArithmeticCompressor {
+  fn default() -> Self {
+    Self::new(FreqTable::default())
+  }
+}
+
+impl ArithmeticCompressor {
+  /// An arithmetic compressor that adapts to the data it codes.<br>
+  /// Both the encoder and decoder bump the frequency of each symbol<br>
+  /// after it's been coded, so frequent symbols cost fewer bits.
+  pub fn adaptive() -> Self {
+    Self::new_adaptive(FreqTable::default())
+  }
+
+  /// A semi-static compressor, coding with the byte frequencies of `items`.<br>
+  /// The frequency table is written ahead of the coded bits, so any<br>
+  /// compressor `with_header` can decode the stream without the sample.
+  pub fn from_sample(items: impl IntoIterator<Item = u8>) -> Self {
+    Self::new(FreqTable::from_sample(items)).with_header()
+  }
+}
+
+impl<F, C> ArithmeticCompressor<F, C> {
+  /// An arithmetic compressor that codes with a fixed model.
+  pub fn new(frequencies: F) -> Self {
+    Self {
+      frequencies,
+      adaptive: false,
+      header: None,
+      symbol: PhantomData,
+    }
+  }
+
+  /// An arithmetic compressor that starts from the model `frequencies`<br>
+  /// and calls `SymbolFreq::increment` after coding each symbol.
+  pub fn new_adaptive(frequencies: F) -> Self {
+    Self {
+      adaptive: true,
+      ..Self::new(frequencies)
+    }
+  }
+}
+
+impl<F, C, const P: u32> ArithmeticCompressor<F, C, P> {
+  /// Codes with `Q` bit code values instead, between 3 and `MAX_PRECISION`.<br>
+  /// Models can then have a total frequency up to `freq_max(Q)`,<br>
+  /// so wider codes suit large or highly skewed models.
+  pub fn with_precision<const Q: u32>(self) -> ArithmeticCompressor<F, C, Q> {
+    const { assert!((3 <= Q) & (Q <= MAX_PRECISION), "unsupported precision") };
+    ArithmeticCompressor {
+      frequencies: self.frequencies,
+      adaptive: self.adaptive,
+      header: self.header,
+      symbol: PhantomData,
+    }
+  }
+}
+
+impl<C: Symbol> ArithmeticCompressor<FenwickTable, C> {
+  /// An adaptive compressor over the first `size` items of the alphabet `C`,<br>
+  /// i.e. those with `Symbol::index` below `size`.
+  ///
+  /// Alphabets of more than `FREQ_MAX / 2` items get a limit of `4 * size`,<br>
+  /// which needs a precision with at least that `freq_max`, see `with_precision`.<br>
+  /// Otherwise encoding fails with `ArithmeticError::InvalidModel`.
+  pub fn with_alphabet(size: usize) -> Self {
+    let limit = match size as Code <= FREQ_MAX / 2 {
+      true => FREQ_MAX,
+      false => (4 * size as Code).min(freq_max(MAX_PRECISION)),
+    };
+    Self::new_adaptive(FenwickTable::with_size_and_limit(size, limit))
+  }
+}
+
+impl<F: FreqHeader, C, const P: u32> ArithmeticCompressor<F, C, P> {
+  /// Writes the model ahead of the coded bits when encoding,<br>
+  /// and reads it back in place of the decoder's own model.
+  pub fn with_header(self) -> Self {
+    Self {
+      header: Some((F::write_header, F::read_header)),
+      ..self
+    }
+  }
 }
 
-unsafe impl Compressor for ArithmeticCompressor {
-  type Error = Infallible;
-  type Item = u8;
+unsafe impl<F: SymbolFreq<C>, C: Copy, const P: u32> Compressor for ArithmeticCompressor<F, C, P> {
+  type Error = ArithmeticError;
+  type Item = C;
   type Data = bool;
 
-  type Encoder = ArithmeticEncoder;
-  type Decoder = ArithmeticDecoder;
+  type Encoder = ArithmeticEncoder<F, C, P>;
+  type Decoder = ArithmeticDecoder<F, C, P>;
 }
 
 #[cfg(test)]
@@ -0,0 +1,38 @@
+/// Items that can be coded through their index in an alphabet.<br>
+/// Models such as `FenwickTable` work on indices, so any type<br>
+/// implementing this can be arithmetic coded, including user enums.
+pub trait Symbol: Copy {
+  /// The index of `self` in the alphabet
+  fn index(self) -> usize;
+  /// The item at `index` in the alphabet, if there is one
+  fn from_index(index: usize) -> Option<Self>;
+}
+
+macro_rules! int_symbol {
+  ($($int:ty),*) => {$(
+    impl Symbol for $int {
+      #[inline]
+      fn index(self) -> usize {
+        self as usize
+      }
+
+      #[inline]
+      fn from_index(index: usize) -> Option<Self> {
+        Self::try_from(index).ok()
+      }
+    }
+  )*};
+}
+int_symbol!(u8, u16, u32, usize);
+
+impl Symbol for char {
+  #[inline]
+  fn index(self) -> usize {
+    self as usize
+  }
+
+  #[inline]
+  fn from_index(index: usize) -> Option<Self> {
+    char::from_u32(u32::try_from(index).ok()?)
+  }
+}
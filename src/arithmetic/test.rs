@@ -1,13 +1,439 @@
-use std::default;
+use crate::{
+  test::{skewed_bytes, CompressorTests},
+  ArithmeticDecoder, ArithmeticEncoder, ArithmeticError, Compressor, FromBits, IntoBits,
+  IteratorTransforms,
+};
+use proptest::{prop_assert_eq, proptest};
+use std::{array, iter};
 
-use crate::{test::CompressorTests, IntoBits, IteratorTransforms, Transform};
-use proptest::proptest;
-
-use super::ArithmeticCompressor;
+use super::{
+  arithmetic_codes::{freq_max, Code, FREQ_MAX, MAX_PRECISION},
+  ArithmeticCompressor, FenwickTable, FreqTable, Symbol, SymbolFreq,
+};
 
 proptest! {
   #[test]
   fn test_encode_decode(items: Vec<u8>) {
     ArithmeticCompressor::default().test_encode_decode(items).unwrap()
   }
+
+  #[test]
+  fn test_adaptive_encode_decode(items: Vec<u8>) {
+    ArithmeticCompressor::adaptive().test_encode_decode(items).unwrap()
+  }
+}
+
+#[test]
+fn test_adaptive_compresses_repeats() {
+  let encoded_len =
+    |compressor: ArithmeticCompressor| [0u8; 1024].into_iter().apply(compressor.encoder()).count();
+
+  let fixed = encoded_len(ArithmeticCompressor::default());
+  let adaptive = encoded_len(ArithmeticCompressor::adaptive());
+  assert!(adaptive < fixed / 4, "{adaptive} bits vs {fixed} bits");
+}
+
+#[test]
+fn test_increment_rescales_full_table() {
+  let mut table = FreqTable::default();
+  for _ in 0..2 * FREQ_MAX {
+    table.increment(0);
+  }
+
+  assert!(table.len() <= FREQ_MAX);
+  for c in 0..=255 {
+    let (low, high) = table.range(c);
+    assert!(low < high, "symbol {c} has no frequency");
+  }
+}
+
+#[test]
+fn test_adaptive_keeps_learning() {
+  // a table that stopped adapting would code the trailing `1`s with
+  // the statistics of the leading `0`s, costing ~15 bits per symbol
+  let items = [0u8; 1 << 16].into_iter().chain([1u8; 1 << 16]);
+  let encoded = items
+    .apply(ArithmeticCompressor::adaptive().encoder())
+    .count();
+  assert!(encoded < 1 << 16, "{encoded} bits");
+}
+
+/// A minimal adaptive model over `bool`s, to check custom models plug in
+#[derive(Clone, Copy)]
+struct BoolFreq([Code; 2]);
+
+impl SymbolFreq<bool> for BoolFreq {
+  fn len(&self) -> Code {
+    self.0[0] + self.0[1] + 1
+  }
+
+  fn range(&self, item: bool) -> (Code, Code) {
+    match item {
+      false => (0, self.0[0]),
+      true => (self.0[0], self.0[0] + self.0[1]),
+    }
+  }
+
+  fn lookup(&self, i: Code) -> Option<((Code, Code), bool)> {
+    let item = match i {
+      i if i < self.0[0] => false,
+      i if i < self.0[0] + self.0[1] => true,
+      _ => return None,
+    };
+    Some((self.range(item), item))
+  }
+
+  fn end(&self) -> (Code, Code) {
+    (self.len() - 1, self.len())
+  }
+
+  fn increment(&mut self, item: bool) {
+    if self.len() < FREQ_MAX {
+      self.0[item as usize] += 1;
+    }
+  }
+}
+
+#[test]
+fn test_custom_model_compresses() {
+  let items = (0..4096).map(|i| i % 64 == 0);
+  let encoded = items
+    .apply(ArithmeticCompressor::new_adaptive(BoolFreq([1, 1])).encoder())
+    .count();
+  assert!(encoded < 4096 / 2, "{encoded} bits");
+}
+
+proptest! {
+  #[test]
+  fn test_custom_model_encode_decode(items: Vec<bool>) {
+    ArithmeticCompressor::new_adaptive(BoolFreq([1, 1]))
+      .test_encode_decode(items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_bytes_encode_decode(items: Vec<u8>) {
+    // the final byte is padded with zeros, which mustn't be decoded
+    let compressor = ArithmeticCompressor::adaptive();
+    let encoded = (items.iter().copied())
+      .apply(<(ArithmeticEncoder, FromBits<u8>)>::from(compressor))
+      .collect::<Result<Vec<_>, _>>()
+      .unwrap();
+    let decoded = (encoded.into_iter())
+      .apply(<(IntoBits<u8>, ArithmeticDecoder)>::from(compressor))
+      .collect::<Result<Vec<_>, _>>()
+      .unwrap();
+    prop_assert_eq!(items, decoded);
+  }
+}
+
+#[test]
+fn test_truncated() {
+  let compressor = ArithmeticCompressor::adaptive();
+  compressor.test_truncated(Vec::new(), 0, ArithmeticError::Truncated);
+  compressor.test_truncated((0..=255).collect(), 64, ArithmeticError::Truncated);
+}
+
+#[test]
+fn test_zero_frequency_item() {
+  ArithmeticCompressor::new(BoolFreq([0, 1]))
+    .test_encode_error(vec![true, false], ArithmeticError::ZeroFrequency);
+}
+
+proptest! {
+  #[test]
+  fn test_decode_arbitrary(bits: Vec<bool>) {
+    ArithmeticCompressor::default().test_decode_arbitrary(bits)
+  }
+
+  #[test]
+  fn test_adaptive_decode_arbitrary(bits: Vec<bool>) {
+    ArithmeticCompressor::adaptive().test_decode_arbitrary(bits)
+  }
+
+  #[test]
+  fn test_custom_model_decode_arbitrary(bits: Vec<bool>) {
+    ArithmeticCompressor::new_adaptive(BoolFreq([1, 1])).test_decode_arbitrary(bits)
+  }
+}
+
+#[test]
+fn test_oversized_model() {
+  let compressor = ArithmeticCompressor::new(BoolFreq([FREQ_MAX, 1]));
+  let encoded = [true]
+    .into_iter()
+    .apply(compressor.encoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(encoded, Err(ArithmeticError::InvalidModel));
+
+  let decoded = [true; 64]
+    .into_iter()
+    .apply(compressor.decoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(decoded, Err(ArithmeticError::InvalidModel));
+}
+
+proptest! {
+  #[test]
+  fn test_from_sample_encode_decode(items: Vec<u8>) {
+    ArithmeticCompressor::from_sample(items.clone())
+      .test_encode_decode(items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_header_decode_without_sample(items: Vec<u8>) {
+    ArithmeticCompressor::from_sample(items.clone())
+      .test_header_decode_without_sample(ArithmeticCompressor::default().with_header(), items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_header_decode_arbitrary(bits: Vec<bool>) {
+    ArithmeticCompressor::default()
+      .with_header()
+      .test_decode_arbitrary(bits)
+  }
+}
+
+#[test]
+fn test_from_sample_compresses_skewed() {
+  ArithmeticCompressor::from_sample(skewed_bytes())
+    .test_compresses_skewed(ArithmeticCompressor::default());
+}
+
+#[test]
+fn test_header_with_zero_counts() {
+  // only even bytes occur, so odd bytes have no frequency to write
+  let counts = array::from_fn(|byte| ((byte + 1) % 2) as Code);
+  let items: Vec<u8> = (0..=255).step_by(2).collect();
+  ArithmeticCompressor::new(FreqTable::from_counts(counts))
+    .with_header()
+    .test_header_decode_without_sample(ArithmeticCompressor::default().with_header(), items)
+    .unwrap();
+}
+
+#[test]
+fn test_invalid_header() {
+  // a gamma code longer than any frequency
+  let decoded = [false; 64]
+    .into_iter()
+    .apply(ArithmeticCompressor::default().with_header().decoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(decoded, Err(ArithmeticError::InvalidHeader));
+}
+
+proptest! {
+  #[test]
+  fn test_fenwick_encode_decode(items: Vec<u8>) {
+    ArithmeticCompressor::new_adaptive(FenwickTable::default())
+      .test_encode_decode(items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_fenwick_matches_freq_table(items: Vec<u8>, repeats in 1..64usize) {
+    // both tables adapt and rescale identically, so should code identically
+    let items = items.repeat(repeats);
+    let fenwick: Vec<_> = (items.iter().copied())
+      .apply(ArithmeticCompressor::new_adaptive(FenwickTable::default()).encoder())
+      .collect::<Result<_, _>>()
+      .unwrap();
+    let table: Vec<_> = (items.iter().copied())
+      .apply(ArithmeticCompressor::adaptive().encoder())
+      .collect::<Result<_, _>>()
+      .unwrap();
+    prop_assert_eq!(fenwick, table);
+  }
+
+  #[test]
+  fn test_fenwick_decode_arbitrary(bits: Vec<bool>) {
+    ArithmeticCompressor::<_, u16>::with_alphabet(1000).test_decode_arbitrary(bits)
+  }
+}
+
+#[test]
+fn test_fenwick_ranges() {
+  let mut table = FenwickTable::new(37);
+  for i in 0..37usize {
+    for _ in 0..i * i {
+      table.increment_index(i);
+    }
+  }
+
+  let mut low = 0;
+  for i in 0..37 {
+    let (l, h) = table.range_of(i);
+    assert_eq!((l, h), (low, low + (i * i) as Code + 1));
+    for target in l..h {
+      assert_eq!(table.lookup_index(target), Some(((l, h), i)));
+    }
+    low = h;
+  }
+  assert_eq!(table.lookup_index(low), None);
+  assert_eq!(table.range_of(37), (0, 0));
+}
+
+#[test]
+fn test_fenwick_rescales_like_freq_table() {
+  let items = (0..1u32 << 16).map(|i| (i * i % 251) as u8);
+  let fenwick = (items.clone())
+    .apply(ArithmeticCompressor::new_adaptive(FenwickTable::default().with_aging(3)).encoder())
+    .collect::<Result<Vec<_>, _>>();
+  let table = items
+    .apply(ArithmeticCompressor::new_adaptive(FreqTable::default().with_aging(3)).encoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(fenwick, table);
+}
+
+/// A user defined alphabet, like the tokens of a dictionary coder
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Token {
+  Literal(u8),
+  Repeat,
+  Reset,
+}
+
+impl Symbol for Token {
+  fn index(self) -> usize {
+    match self {
+      Token::Literal(byte) => byte as usize,
+      Token::Repeat => 256,
+      Token::Reset => 257,
+    }
+  }
+
+  fn from_index(index: usize) -> Option<Self> {
+    match index {
+      256 => Some(Token::Repeat),
+      257 => Some(Token::Reset),
+      _ => u8::try_from(index).ok().map(Token::Literal),
+    }
+  }
+}
+
+proptest! {
+  #[test]
+  fn test_u16_alphabet_encode_decode(items: Vec<u16>) {
+    let items = items.into_iter().map(|item| item % 4096).collect();
+    ArithmeticCompressor::with_alphabet(4096)
+      .test_encode_decode(items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_char_alphabet_encode_decode(text in "[ -~]*") {
+    ArithmeticCompressor::with_alphabet(128)
+      .test_encode_decode(text.chars().collect())
+      .unwrap()
+  }
+
+  #[test]
+  fn test_enum_alphabet_encode_decode(items: Vec<(u8, u8)>) {
+    let items = (items.into_iter())
+      .map(|(kind, byte)| match kind % 4 {
+        0 => Token::Repeat,
+        1 => Token::Reset,
+        _ => Token::Literal(byte),
+      })
+      .collect();
+    ArithmeticCompressor::with_alphabet(258)
+      .test_encode_decode(items)
+      .unwrap()
+  }
+}
+
+proptest! {
+  #[test]
+  fn test_full_u16_alphabet_encode_decode(items: Vec<u16>) {
+    ArithmeticCompressor::with_alphabet(1 << 16)
+      .with_precision::<24>()
+      .test_encode_decode(items)
+      .unwrap()
+  }
+}
+
+#[test]
+fn test_full_u16_alphabet_needs_precision() {
+  let encoded = [0u16, u16::MAX]
+    .into_iter()
+    .apply(ArithmeticCompressor::with_alphabet(1 << 16).encoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(encoded, Err(ArithmeticError::InvalidModel));
+}
+
+#[test]
+fn test_item_outside_alphabet() {
+  let encoded = "abc\u{80}"
+    .chars()
+    .apply(ArithmeticCompressor::with_alphabet(128).encoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(encoded, Err(ArithmeticError::ZeroFrequency));
+}
+
+proptest! {
+  #[test]
+  fn test_max_precision_encode_decode(items: Vec<u8>) {
+    ArithmeticCompressor::adaptive()
+      .with_precision::<MAX_PRECISION>()
+      .test_encode_decode(items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_max_precision_decode_arbitrary(bits: Vec<bool>) {
+    ArithmeticCompressor::adaptive()
+      .with_precision::<MAX_PRECISION>()
+      .test_decode_arbitrary(bits)
+  }
+}
+
+#[test]
+fn test_wide_precision_compresses_skewed() {
+  // with room for larger counts, the model gets far more confident
+  // in `0` than rescaling at `FREQ_MAX` ever allows
+  let items = || iter::repeat_n(0u8, 1 << 20);
+  let narrow = items()
+    .apply(ArithmeticCompressor::adaptive().encoder())
+    .count();
+
+  let table = FreqTable::default().with_limit(freq_max(MAX_PRECISION));
+  let wide = items()
+    .apply(
+      ArithmeticCompressor::new_adaptive(table)
+        .with_precision::<MAX_PRECISION>()
+        .encoder(),
+    )
+    .count();
+  assert!(wide < narrow / 4, "{wide} bits vs {narrow} bits");
+}
+
+proptest! {
+  #[test]
+  fn test_large_fenwick_encode_decode(items: Vec<u16>) {
+    // twice `FREQ_MAX` symbols, which only a wider precision can code
+    let size = 2 * FREQ_MAX as usize;
+    let items = items.into_iter().map(|item| item % size as u16).collect();
+    let table = FenwickTable::with_size_and_limit(size, 4 * size as Code);
+    ArithmeticCompressor::<_, u16>::new_adaptive(table)
+      .with_precision::<24>()
+      .test_encode_decode(items)
+      .unwrap()
+  }
+}
+
+#[test]
+#[should_panic(expected = "too large for a limit")]
+fn test_size_beyond_limit() {
+  FenwickTable::with_size_and_limit(1000, 1999);
+}
+
+#[test]
+fn test_limit_beyond_precision() {
+  let table = FreqTable::default().with_limit(2 * FREQ_MAX);
+  let encoded = [0u8; FREQ_MAX as usize]
+    .into_iter()
+    .apply(ArithmeticCompressor::new_adaptive(table).encoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(encoded, Err(ArithmeticError::InvalidModel));
 }
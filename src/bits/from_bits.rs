@@ -27,6 +27,19 @@ impl IntFromBits for u32 {
   const BITS: u32 = u32::BITS;
 }
 
+impl<D: IntFromBits> FromBits<D> {
+  /// Reads a value from the next `width` bits, for reading values of varying width.<br>
+  /// Returns `None` if the bits run out first.
+  pub fn read(width: u32, iter: &mut impl Iterator<Item = bool>) -> Option<D> {
+    assert!(width <= D::BITS, "width must be within the type");
+    let mut value = D::from(false);
+    for i in (0..width).rev() {
+      value |= D::from(iter.next()?) << i;
+    }
+    Some(value)
+  }
+}
+
 impl<D: IntFromBits> Transform<bool> for FromBits<D> {
   type Out = D;
   fn next(&mut self, iter: &mut impl Iterator<Item = bool>) -> Option<Self::Out> {
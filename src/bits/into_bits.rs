@@ -33,7 +33,20 @@ impl IntIntoBits for u32 {
 }
 
 impl<D: IntIntoBits> IntoBits<D> {
-  fn next_bit(&mut self) -> Option<bool> {
+  /// Queues the low `width` bits of `data`, for writing values of varying width.<br>
+  /// Any bits still queued are dropped.
+  pub fn push(&mut self, data: D, width: u32) {
+    assert!(
+      0 < width && width <= D::BITS,
+      "width must be within the type"
+    );
+    self.data = data;
+    self.data <<= D::BITS - width;
+    self.idx = width;
+  }
+
+  /// The next queued bit, if there is one
+  pub(crate) fn next_bit(&mut self) -> Option<bool> {
     if self.idx == 0 {
       return None;
     }
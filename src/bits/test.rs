@@ -1,4 +1,4 @@
-use super::BitConverter;
+use super::{BitConverter, FromBits, IntoBits};
 use crate::test::CompressorTests;
 use proptest::proptest;
 
@@ -13,3 +13,37 @@ proptest! {
         BitConverter::default().test_encode_decode(items).unwrap()
     }
 }
+
+proptest! {
+    #[test]
+    fn test_decode_arbitrary_u8(bits: Vec<bool>) {
+        BitConverter::<u8>::default().test_decode_arbitrary(bits)
+    }
+
+    #[test]
+    fn test_decode_arbitrary_u32(bits: Vec<bool>) {
+        BitConverter::<u32>::default().test_decode_arbitrary(bits)
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_varying_widths(values: Vec<(u32, u32)>) {
+        // each value is cut down to its width before writing
+        let values: Vec<_> = (values.into_iter())
+            .map(|(value, width)| (value >> (width % 32), 32 - width % 32))
+            .collect();
+        let mut bits = Vec::new();
+        for &(value, width) in &values {
+            let mut into = IntoBits::default();
+            into.push(value, width);
+            bits.extend(std::iter::from_fn(|| into.next_bit()));
+        }
+
+        let mut iter = bits.into_iter();
+        for (value, width) in values {
+            assert_eq!(FromBits::read(width, &mut iter), Some(value));
+        }
+        assert_eq!(iter.next(), None);
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+
+use super::{Bwt, BwtError};
+use crate::{utils::end_after, Transform};
+
+/// The inverse Burrows-Wheeler transform
+pub struct BwtDecoder {
+  block_size: usize,
+  decoded: VecDeque<u8>,
+  ended: bool,
+}
+
+impl From<Bwt> for BwtDecoder {
+  fn from(value: Bwt) -> Self {
+    Self {
+      block_size: value.block_size,
+      decoded: VecDeque::new(),
+      ended: false,
+    }
+  }
+}
+
+impl BwtDecoder {
+  /// Decodes the next block, or `None` if there are no more
+  fn decode<E: From<BwtError>>(
+    &mut self,
+    iter: &mut impl Iterator<Item = Result<u8, E>>,
+  ) -> Result<Option<()>, E> {
+    let header: Vec<_> = iter.take(4).collect::<Result<_, _>>()?;
+    let Ok(header) = <[u8; 4]>::try_from(header.as_slice()) else {
+      return match header.is_empty() {
+        true => Ok(None),
+        false => Err(BwtError::Truncated.into()),
+      };
+    };
+    let primary = u32::from_be_bytes(header) as usize;
+    let block: Vec<_> = iter.take(self.block_size).collect::<Result<_, _>>()?;
+    if primary == 0 || primary > block.len() {
+      return Err(BwtError::InvalidIndex.into());
+    }
+
+    // rows are numbered with the sentinel at `primary`
+    let mut starts = [1; 256];
+    let mut ranks = Vec::with_capacity(block.len());
+    let mut counts = [0; 256];
+    for &byte in &block {
+      ranks.push(counts[byte as usize]);
+      counts[byte as usize] += 1;
+    }
+    for byte in 1..256 {
+      starts[byte] = starts[byte - 1] + counts[byte - 1];
+    }
+
+    // walk backwards from the sentinel's suffix, one byte per row
+    let mut decoded = vec![0; block.len()];
+    let mut row = 0;
+    for byte in decoded.iter_mut().rev() {
+      if row == primary {
+        return Err(BwtError::InvalidIndex.into());
+      }
+      let i = row - usize::from(row > primary);
+      *byte = block[i];
+      row = starts[block[i] as usize] + ranks[i];
+    }
+    self.decoded.extend(decoded);
+    Ok(Some(()))
+  }
+}
+
+impl<E: From<BwtError>> Transform<Result<u8, E>> for BwtDecoder {
+  type Out = Result<u8, E>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = Result<u8, E>>) -> Option<Self::Out> {
+    loop {
+      if let Some(byte) = self.decoded.pop_front() {
+        return Some(Ok(byte));
+      }
+      if self.ended {
+        return None;
+      }
+
+      let decoded = self.decode(iter);
+      if let Some(Err(e)) = end_after(&mut self.ended, decoded) {
+        return Some(Err(e));
+      }
+    }
+  }
+}
+
+impl Transform<u8> for BwtDecoder {
+  type Out = Result<u8, BwtError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    Transform::<Result<u8, BwtError>>::next(self, &mut iter.map(Ok))
+  }
+}
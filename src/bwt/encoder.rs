@@ -0,0 +1,51 @@
+use std::collections::VecDeque;
+
+use super::Bwt;
+use crate::{suffix_array, Transform};
+
+/// The forward Burrows-Wheeler transform
+pub struct BwtEncoder {
+  block_size: usize,
+  encoded: VecDeque<u8>,
+}
+
+impl From<Bwt> for BwtEncoder {
+  fn from(value: Bwt) -> Self {
+    Self {
+      block_size: value.block_size,
+      encoded: VecDeque::new(),
+    }
+  }
+}
+
+impl BwtEncoder {
+  /// Queues the primary index and permuted bytes for `block`.<br>
+  /// The block is treated as ending with a sentinel below every byte,<br>
+  /// which takes the place of the first byte's predecessor.
+  fn encode(&mut self, block: &[u8]) {
+    let suffixes = suffix_array(block);
+
+    // the sentinel's own suffix sorts first, and is preceded by the last byte
+    let Some(first) = suffixes.iter().position(|&suffix| suffix == 0) else {
+      unreachable!("the suffix array holds every suffix")
+    };
+    let primary = 1 + first;
+    self.encoded.extend((primary as u32).to_be_bytes());
+    self.encoded.push_back(block[block.len() - 1]);
+    (self.encoded)
+      .extend((suffixes.iter()).filter_map(|&suffix| suffix.checked_sub(1).map(|i| block[i])));
+  }
+}
+
+impl Transform<u8> for BwtEncoder {
+  type Out = u8;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    if self.encoded.is_empty() {
+      let block: Vec<_> = iter.take(self.block_size).collect();
+      if !block.is_empty() {
+        self.encode(&block);
+      }
+    }
+    self.encoded.pop_front()
+  }
+}
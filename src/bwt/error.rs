@@ -0,0 +1,33 @@
+use std::{error::Error, fmt};
+
+use crate::ArithmeticError;
+
+/// Errors produced when inverting the Burrows-Wheeler transform fails
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BwtError {
+  /// A block's primary index doesn't lead back through the whole block
+  InvalidIndex,
+  /// The bytes ran out partway through a block's primary index
+  Truncated,
+}
+
+impl fmt::Display for BwtError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidIndex => write!(f, "primary index doesn't match the block"),
+      Self::Truncated => write!(f, "stream ended inside a block header"),
+    }
+  }
+}
+
+impl Error for BwtError {}
+
+/// Lets the inverse follow decoders using `ArithmeticError`
+impl From<BwtError> for ArithmeticError {
+  fn from(value: BwtError) -> Self {
+    match value {
+      BwtError::InvalidIndex => Self::InvalidHeader,
+      BwtError::Truncated => Self::Truncated,
+    }
+  }
+}
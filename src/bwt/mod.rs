@@ -0,0 +1,55 @@
+mod error;
+pub use error::BwtError;
+mod encoder;
+pub use encoder::BwtEncoder;
+mod decoder;
+pub use decoder::BwtDecoder;
+
+/// The Burrows-Wheeler transform, see Burrows & Wheeler's<br>
+/// [report](https://www.hpl.hp.com/techreports/Compaq-DEC/SRC-RR-124.pdf) for details.
+///
+/// Bytes are split into blocks, and each block is replaced by the byte<br>
+/// before each of its suffixes, in sorted order of the suffixes. Bytes<br>
+/// followed by similar contexts end up together, which suits move-to-front<br>
+/// and entropy coding afterwards.
+///
+/// Every block is written as a 4 byte big endian primary index, the row<br>
+/// of the whole block, followed by the permuted block.
+#[derive(Clone, Copy)]
+pub struct Bwt {
+  block_size: usize,
+}
+
+impl Default for Bwt {
+  fn default() -> Self {
+    Self {
+      block_size: 900_000,
+    }
+  }
+}
+
+impl Bwt {
+  /// Sets the largest block, `900_000` bytes by default as in bzip2.<br>
+  /// Larger blocks take more memory but gather more similar contexts.
+  pub fn with_block_size(self, block_size: usize) -> Self {
+    assert!(block_size > 0, "blocks must hold at least one byte");
+    assert!(
+      block_size < u32::MAX as usize,
+      "primary indices must fit in 4 bytes"
+    );
+    Self { block_size }
+  }
+
+  /// The forward transform
+  pub fn encoder(self) -> BwtEncoder {
+    self.into()
+  }
+
+  /// The inverse transform
+  pub fn decoder(self) -> BwtDecoder {
+    self.into()
+  }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,89 @@
+use crate::{ArithmeticCompressor, ArithmeticError, Compressor, IteratorTransforms, Transform};
+use proptest::{collection::vec, proptest};
+
+use super::{Bwt, BwtError};
+
+/// Applies the transform and its inverse to `items`
+fn round_trip(bwt: Bwt, items: &[u8]) -> Result<Vec<u8>, BwtError> {
+  (items.iter().copied())
+    .apply(bwt.encoder())
+    .apply(bwt.decoder())
+    .collect()
+}
+
+proptest! {
+  #[test]
+  fn test_encode_decode(items: Vec<u8>) {
+    assert_eq!(round_trip(Bwt::default(), &items), Ok(items))
+  }
+
+  #[test]
+  fn test_blocks_encode_decode(items in vec(0..4u8, 0..1024), block_size in 1..256usize) {
+    let bwt = Bwt::default().with_block_size(block_size);
+    assert_eq!(round_trip(bwt, &items), Ok(items))
+  }
+
+  #[test]
+  fn test_decode_arbitrary(bytes: Vec<u8>, block_size in 1..16usize) {
+    let decoder = Bwt::default().with_block_size(block_size).decoder();
+    for decoded in bytes.into_iter().apply(decoder) {
+      if decoded.is_err() {
+        break;
+      }
+    }
+  }
+}
+
+#[test]
+fn test_banana() {
+  let encoded: Vec<_> = b"banana"
+    .iter()
+    .copied()
+    .apply(Bwt::default().encoder())
+    .collect();
+  assert_eq!(encoded, [&[0, 0, 0, 4], b"annbaa".as_slice()].concat());
+}
+
+#[test]
+fn test_invalid_blocks() {
+  let decode = |bytes: &[u8]| {
+    (bytes.iter().copied())
+      .apply(Bwt::default().decoder())
+      .collect::<Result<Vec<_>, _>>()
+  };
+
+  assert_eq!(
+    decode(&[0, 0, 0, 4, b'a', b'n', b'n', b'b', b'a', b'a']),
+    Ok(b"banana".to_vec())
+  );
+  assert_eq!(decode(&[0, 0, 0, 0, b'a']), Err(BwtError::InvalidIndex));
+  assert_eq!(decode(&[0, 0, 0, 2, b'a']), Err(BwtError::InvalidIndex));
+  // the walk from the sentinel comes back to it after one byte
+  assert_eq!(
+    decode(&[0, 0, 0, 1, b'a', b'b']),
+    Err(BwtError::InvalidIndex)
+  );
+  assert_eq!(decode(&[0, 0]), Err(BwtError::Truncated));
+}
+
+#[test]
+fn test_then_entropy_coder() {
+  let text = "she sells sea shells by the sea shore. ".repeat(64);
+  let bwt = Bwt::default().with_block_size(1024);
+  let encoded: Vec<_> = (text.bytes())
+    .apply(
+      bwt
+        .encoder()
+        .then(ArithmeticCompressor::adaptive().encoder()),
+    )
+    .collect::<Result<_, _>>()
+    .unwrap();
+  let decoded = (encoded.into_iter())
+    .apply(
+      ArithmeticCompressor::adaptive()
+        .decoder()
+        .then(bwt.decoder()),
+    )
+    .collect::<Result<Vec<_>, ArithmeticError>>();
+  assert_eq!(decoded, Ok(text.into_bytes()));
+}
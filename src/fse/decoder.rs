@@ -0,0 +1,88 @@
+use super::{
+  table::{read_bits, FseTable},
+  FseCompressor,
+};
+use crate::{
+  arithmetic::try_read_gamma, utils::end_after, ArithmeticError, Code, FreqHeader, Transform,
+};
+
+/// FSE Decoding algorithm
+pub struct FseDecoder {
+  table: FseTable,
+  /// Whether the table should be read from the stream first
+  header: bool,
+  state: Code,
+  /// Items left to decode in the current block
+  remaining: Code,
+  ended: bool,
+}
+
+impl From<FseCompressor> for FseDecoder {
+  fn from(value: FseCompressor) -> Self {
+    Self {
+      table: value.table,
+      header: value.header,
+      state: 0,
+      remaining: 0,
+      ended: false,
+    }
+  }
+}
+
+impl FseDecoder {
+  /// Reads the next block's length and initial state.<br>
+  /// Returns `false` at the empty block marking the end of the stream.
+  fn start_block(
+    &mut self,
+    iter: &mut impl Iterator<Item = bool>,
+  ) -> Result<bool, ArithmeticError> {
+    // decoding a block in full always returns the state to where encoding began
+    if self.state != 0 {
+      return Err(ArithmeticError::OutOfRange);
+    }
+
+    let length = try_read_gamma(
+      iter,
+      ArithmeticError::Truncated,
+      ArithmeticError::InvalidHeader,
+    )?;
+    self.remaining = length - 1;
+    if self.remaining == 0 {
+      return Ok(false);
+    }
+
+    self.state = read_bits(self.table.table_log(), iter).ok_or(ArithmeticError::Truncated)?;
+    Ok(true)
+  }
+
+  /// Decodes the next byte, or `None` after the last block
+  fn decode(
+    &mut self,
+    iter: &mut impl Iterator<Item = bool>,
+  ) -> Result<Option<u8>, ArithmeticError> {
+    if self.header {
+      self.header = false;
+      self.table = FseTable::read_header(iter).ok_or(ArithmeticError::InvalidHeader)?;
+    }
+    if (self.remaining == 0) && !self.start_block(iter)? {
+      return Ok(None);
+    }
+
+    let (byte, state) = (self.table.decode(self.state, iter)).ok_or(ArithmeticError::Truncated)?;
+    self.state = state;
+    self.remaining -= 1;
+    Ok(Some(byte))
+  }
+}
+
+impl Transform<bool> for FseDecoder {
+  type Out = Result<u8, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = bool>) -> Option<Self::Out> {
+    if self.ended {
+      return None;
+    }
+
+    let decoded = self.decode(iter);
+    end_after(&mut self.ended, decoded)
+  }
+}
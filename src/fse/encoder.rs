@@ -0,0 +1,82 @@
+use super::{FseCompressor, FseTable};
+use crate::{arithmetic::write_gamma, ArithmeticError, Code, FreqHeader, Transform};
+use std::collections::VecDeque;
+
+/// FSE Encoding algorithm
+pub struct FseEncoder {
+  table: FseTable,
+  block_size: usize,
+  /// Coded bits of the header and current block, ready to be output
+  output: VecDeque<bool>,
+  ended: bool,
+  failed: bool,
+}
+
+impl From<FseCompressor> for FseEncoder {
+  fn from(value: FseCompressor) -> Self {
+    let mut header = Vec::new();
+    if value.header {
+      value.table.write_header(&mut header);
+    }
+
+    Self {
+      table: value.table,
+      block_size: value.block_size,
+      output: header.into(),
+      ended: false,
+      failed: false,
+    }
+  }
+}
+
+impl FseEncoder {
+  /// Encodes `block` in reverse, so that it decodes forwards.<br>
+  /// Bits are pushed onto `stack` in the reverse of the order they're read.
+  fn encode(&self, block: &[u8], stack: &mut Vec<bool>) -> Result<(), ArithmeticError> {
+    let size = self.table.size();
+    let mut state = size;
+    for &byte in block.iter().rev() {
+      state = (self.table.encode(state, byte, stack)).ok_or(ArithmeticError::ZeroFrequency)?;
+    }
+
+    let state: Code = state - size;
+    stack.extend((0..self.table.table_log()).map(|i| (state >> i) & 1 == 1));
+    Ok(())
+  }
+}
+
+impl Transform<u8> for FseEncoder {
+  type Out = Result<bool, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    if self.failed {
+      return None;
+    }
+
+    loop {
+      if let Some(bit) = self.output.pop_front() {
+        return Some(Ok(bit));
+      }
+
+      if self.ended {
+        return None;
+      }
+
+      // an empty block marks the end of the stream
+      let block: Vec<_> = iter.by_ref().take(self.block_size).collect();
+      let mut length = Vec::new();
+      write_gamma(block.len() as Code + 1, &mut length);
+      self.output.extend(length);
+      if block.is_empty() {
+        self.ended = true;
+        continue;
+      }
+
+      let mut stack = Vec::new();
+      if let Err(e) = self.encode(&block, &mut stack) {
+        self.failed = true;
+        return Some(Err(e));
+      }
+      self.output.extend(stack.into_iter().rev());
+    }
+  }
+}
@@ -0,0 +1,74 @@
+use crate::{ArithmeticError, Compressor, FreqTable};
+
+mod table;
+pub use table::FseTable;
+mod encoder;
+pub use encoder::FseEncoder;
+mod decoder;
+pub use decoder::FseDecoder;
+
+/// Finite state entropy, Collet's tabled variant of asymmetric numeral systems.<br>
+/// See Duda's [paper](https://arxiv.org/abs/1311.2540) and Collet's [blog](https://fastcompression.blogspot.com/2013/12/finite-state-entropy-new-breed-of.html)<br>
+/// for details, the tables here are built the same way as zstd's.
+///
+/// Like rANS, items are decoded in the reverse order they're encoded,<br>
+/// so the encoder buffers blocks of items, each output with its length.
+#[derive(Clone)]
+pub struct FseCompressor {
+  table: FseTable,
+  header: bool,
+  block_size: usize,
+}
+
+impl Default for FseCompressor {
+  fn default() -> Self {
+    Self::new(FseTable::default())
+  }
+}
+
+impl FseCompressor {
+  /// An FSE compressor coding with the normalized counts in `table`
+  pub fn new(table: FseTable) -> Self {
+    Self {
+      table,
+      header: false,
+      block_size: 1 << 16,
+    }
+  }
+
+  /// A compressor coding with the byte frequencies of `items`,<br>
+  /// writing its table ahead of the coded bits.
+  pub fn from_sample(items: impl IntoIterator<Item = u8>) -> Self {
+    let frequencies = FreqTable::from_sample(items);
+    Self::new(FseTable::new(&frequencies, 11)).with_header()
+  }
+
+  /// Writes the table ahead of the coded bits when encoding,<br>
+  /// and reads it back in place of the decoder's own table.
+  pub fn with_header(self) -> Self {
+    Self {
+      header: true,
+      ..self
+    }
+  }
+
+  /// Sets the most items buffered and coded at once, `1 << 16` by default.<br>
+  /// Blocks are coded last item first, so none of a block's bits are output<br>
+  /// until all of it is read, and each ends by flushing `table_log` bits of state.
+  pub fn with_block_size(self, block_size: usize) -> Self {
+    assert!(block_size > 0, "blocks must hold at least one item");
+    Self { block_size, ..self }
+  }
+}
+
+unsafe impl Compressor for FseCompressor {
+  type Error = ArithmeticError;
+  type Item = u8;
+  type Data = bool;
+
+  type Encoder = FseEncoder;
+  type Decoder = FseDecoder;
+}
+
+#[cfg(test)]
+mod test;
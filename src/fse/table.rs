@@ -0,0 +1,187 @@
+use crate::{
+  arithmetic::{read_gamma, write_gamma},
+  Code, FreqHeader, FreqTable,
+};
+
+/// The smallest and largest supported table logs.<br>
+/// Every byte needs a state, and the spread step needs at least 16 states.
+const TABLE_LOG: (u32, u32) = (8, 15);
+
+/// Byte counts normalized to sum to `1 << table_log`, along with<br>
+/// the state transition tables built from them.
+#[derive(Clone)]
+pub struct FseTable {
+  table_log: u32,
+  counts: [Code; 256],
+  /// The sum of the counts of the bytes before each byte
+  cumulative: [Code; 256],
+  /// Each byte's encoding states, in the order they appear in the spread
+  states: Vec<Code>,
+  /// For each decoding state: its byte, the bits to read and the next state's base
+  decode: Vec<(u8, u32, Code)>,
+}
+
+impl Default for FseTable {
+  fn default() -> Self {
+    Self::new(&FreqTable::default(), 11)
+  }
+}
+
+/// The position of the highest set bit in `value > 0`
+fn high_bit(value: Code) -> u32 {
+  Code::BITS - 1 - value.leading_zeros()
+}
+
+impl FseTable {
+  /// A table over `1 << table_log` states, normalized from `frequencies`.
+  ///
+  /// ## Panics
+  ///
+  /// If `table_log` is outside `8..=15`.
+  pub fn new(frequencies: &FreqTable, table_log: u32) -> Self {
+    assert!(
+      (TABLE_LOG.0..=TABLE_LOG.1).contains(&table_log),
+      "table log of {table_log} is outside 8..=15"
+    );
+    Self::build(table_log, frequencies.quantized(table_log))
+  }
+
+  /// A table from counts already normalized to sum to `1 << table_log`,<br>
+  /// such as those from another FSE stream. Returns `None` if they don't,<br>
+  /// or if `table_log` is outside `8..=15`.
+  pub fn from_normalized(table_log: u32, counts: [Code; 256]) -> Option<Self> {
+    if !(TABLE_LOG.0..=TABLE_LOG.1).contains(&table_log) {
+      return None;
+    }
+    let total = (counts.iter()).try_fold(0, |total: Code, &count| total.checked_add(count));
+    (total == Some(1 << table_log)).then(|| Self::build(table_log, counts))
+  }
+
+  /// The number of states, `1 << table_log`
+  pub fn size(&self) -> Code {
+    1 << self.table_log
+  }
+
+  /// The normalized count of each byte
+  pub fn counts(&self) -> [Code; 256] {
+    self.counts
+  }
+
+  /// Builds the tables from `counts`, which must sum to `1 << table_log`
+  fn build(table_log: u32, counts: [Code; 256]) -> Self {
+    let size = 1 << table_log;
+
+    // spread each byte's states across the table, so that
+    // states for the same byte are far apart
+    let mut spread = vec![0; size];
+    let step = (size >> 1) + (size >> 3) + 3;
+    let mut position = 0;
+    for (byte, count) in counts.into_iter().enumerate() {
+      for _ in 0..count {
+        spread[position] = byte as u8;
+        position = (position + step) & (size - 1);
+      }
+    }
+
+    let mut cumulative = [0; 256];
+    for byte in 1..256 {
+      cumulative[byte] = cumulative[byte - 1] + counts[byte - 1];
+    }
+
+    let mut next = counts;
+    let mut states = vec![0; size];
+    let mut decode = Vec::with_capacity(size);
+    for (position, &byte) in spread.iter().enumerate() {
+      let byte_index = byte as usize;
+      let occurrence = next[byte_index];
+      next[byte_index] += 1;
+
+      // the `occurrence - count`th state for the byte sits at `position`
+      let index = cumulative[byte_index] + occurrence - counts[byte_index];
+      states[index as usize] = (size + position) as Code;
+
+      let bits = table_log - high_bit(occurrence);
+      decode.push((byte, bits, (occurrence << bits) - size as Code));
+    }
+
+    Self {
+      table_log,
+      counts,
+      cumulative,
+      states,
+      decode,
+    }
+  }
+
+  /// Encodes `byte` from `state` in `size..2 * size`, pushing the bits<br>
+  /// shifted out onto `stack` last bit first. Returns the previous state,<br>
+  /// or `None` if `byte` has a count of zero.
+  pub(crate) fn encode(&self, state: Code, byte: u8, stack: &mut Vec<bool>) -> Option<Code> {
+    let count = self.counts[byte as usize];
+    if count == 0 {
+      return None;
+    }
+
+    // shift until the state is within `count..2 * count`
+    let max_bits = self.table_log - high_bit(count);
+    let bits = match state < (count << max_bits) {
+      true => max_bits - 1,
+      false => max_bits,
+    };
+    stack.extend((0..bits).map(|i| (state >> i) & 1 == 1));
+
+    let index = self.cumulative[byte as usize] + (state >> bits) - count;
+    Some(self.states[index as usize])
+  }
+
+  /// Decodes the byte for `state` in `0..size`, reading the bits<br>
+  /// to find the next state. Returns `None` if the bits run out.
+  pub(crate) fn decode(
+    &self,
+    state: Code,
+    iter: &mut impl Iterator<Item = bool>,
+  ) -> Option<(u8, Code)> {
+    let (byte, bits, base) = self.decode[state as usize];
+    Some((byte, base + read_bits(bits, iter)?))
+  }
+
+  /// The table's log, in the bits used to write a state
+  pub(crate) fn table_log(&self) -> u32 {
+    self.table_log
+  }
+}
+
+/// Reads `bits` bits, most significant first
+pub(crate) fn read_bits(bits: u32, iter: &mut dyn Iterator<Item = bool>) -> Option<Code> {
+  let mut value = 0;
+  for _ in 0..bits {
+    value = (value << 1) | Code::from(iter.next()?);
+  }
+  Some(value)
+}
+
+/// The table log in 4 bits, then each byte's count as an Elias gamma code.
+impl FreqHeader for FseTable {
+  fn write_header(&self, bits: &mut Vec<bool>) {
+    bits.extend((0..4).rev().map(|i| (self.table_log >> i) & 1 == 1));
+    for count in self.counts {
+      write_gamma(count + 1, bits);
+    }
+  }
+
+  fn read_header(bits: &mut dyn Iterator<Item = bool>) -> Option<Self> {
+    let table_log = read_bits(4, bits)? as u32;
+    if !(TABLE_LOG.0..=TABLE_LOG.1).contains(&table_log) {
+      return None;
+    }
+
+    // stop early on counts too large for the table
+    let mut counts = [0; 256];
+    let mut total: Code = 0;
+    for count in &mut counts {
+      *count = read_gamma(bits)? - 1;
+      total = (total.checked_add(*count)).filter(|t| *t <= 1 << table_log)?;
+    }
+    Self::from_normalized(table_log, counts)
+  }
+}
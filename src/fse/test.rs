@@ -0,0 +1,115 @@
+use crate::{
+  test::{skewed_bytes, CompressorTests},
+  ArithmeticError, Code, Compressor, FreqHeader, FreqTable, IteratorTransforms,
+};
+use proptest::{prop_assert_eq, proptest};
+
+use super::{FseCompressor, FseTable};
+
+proptest! {
+  #[test]
+  fn test_encode_decode(items: Vec<u8>) {
+    FseCompressor::default().test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_from_sample_encode_decode(items: Vec<u8>) {
+    FseCompressor::from_sample(items.clone())
+      .test_encode_decode(items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_table_log_encode_decode(items: Vec<u8>, table_log in 8..=15u32) {
+    let frequencies = FreqTable::from_sample(items.clone());
+    FseCompressor::new(FseTable::new(&frequencies, table_log))
+      .with_block_size(37)
+      .test_encode_decode(items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_header_decode_without_sample(items: Vec<u8>) {
+    FseCompressor::from_sample(items.clone())
+      .test_header_decode_without_sample(FseCompressor::default().with_header(), items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_header_round_trip(items: Vec<u8>, table_log in 8..=15u32) {
+    let table = FseTable::new(&FreqTable::from_sample(items), table_log);
+    let mut bits = Vec::new();
+    table.write_header(&mut bits);
+    let read = FseTable::read_header(&mut bits.into_iter()).unwrap();
+    prop_assert_eq!(table.size(), read.size());
+    prop_assert_eq!(table.counts(), read.counts());
+  }
+}
+
+#[test]
+fn test_from_normalized() {
+  let table = FseTable::new(&FreqTable::from_sample(*b"abracadabra"), 9);
+  let rebuilt = FseTable::from_normalized(9, table.counts()).unwrap();
+  assert_eq!(rebuilt.counts(), table.counts());
+  let encoded: Vec<_> = (b"abracadabra".iter().copied())
+    .apply(FseCompressor::new(table).encoder())
+    .collect::<Result<_, _>>()
+    .unwrap();
+  let decoded = (encoded.into_iter())
+    .apply(FseCompressor::new(rebuilt).decoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(decoded, Ok(b"abracadabra".to_vec()));
+
+  let mut counts = [0; 256];
+  counts[0] = 1 << 11;
+  assert!(FseTable::from_normalized(11, counts).is_some());
+  assert!(FseTable::from_normalized(10, counts).is_none());
+  assert!(FseTable::from_normalized(16, [256; 256]).is_none());
+  assert!(FseTable::from_normalized(7, [0; 256]).is_none());
+  assert!(FseTable::from_normalized(11, [Code::MAX; 256]).is_none());
+}
+
+#[test]
+fn test_from_sample_compresses_skewed() {
+  FseCompressor::from_sample(skewed_bytes()).test_compresses_skewed(FseCompressor::default());
+}
+
+#[test]
+fn test_zero_frequency_item() {
+  let mut counts = [1; 256];
+  counts[1] = 0;
+  let table = FseTable::new(&FreqTable::from_counts(counts), 11);
+  FseCompressor::new(table).test_encode_error(vec![0, 1], ArithmeticError::ZeroFrequency);
+}
+
+#[test]
+fn test_truncated() {
+  let compressor = FseCompressor::default();
+  (compressor.clone()).test_truncated(Vec::new(), 0, ArithmeticError::Truncated);
+  compressor.test_truncated((0..=255).collect(), 512, ArithmeticError::Truncated);
+}
+
+#[test]
+fn test_invalid_block_length() {
+  // a gamma code longer than any block length
+  let decoded = [false; 80]
+    .into_iter()
+    .apply(FseCompressor::default().decoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(decoded, Err(ArithmeticError::InvalidHeader));
+}
+
+proptest! {
+  #[test]
+  fn test_decode_arbitrary(bits: Vec<bool>) {
+    FseCompressor::default().test_decode_arbitrary(bits)
+  }
+
+  #[test]
+  fn test_header_decode_arbitrary(bits: Vec<bool>) {
+    FseCompressor::default()
+      .with_header()
+      .with_block_size(5)
+      .test_decode_arbitrary(bits)
+  }
+}
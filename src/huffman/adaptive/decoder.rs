@@ -0,0 +1,40 @@
+use super::{AdaptiveHuffmanCompressor, FgkTree};
+use crate::{huffman::table::END, utils::end_after, ArithmeticError, Transform};
+
+/// Adaptive Huffman Decoding algorithm
+#[derive(Default)]
+pub struct AdaptiveHuffmanDecoder {
+  tree: FgkTree,
+  ended: bool,
+}
+
+impl From<AdaptiveHuffmanCompressor> for AdaptiveHuffmanDecoder {
+  fn from(_: AdaptiveHuffmanCompressor) -> Self {
+    Self::default()
+  }
+}
+
+impl AdaptiveHuffmanDecoder {
+  /// Decodes the next byte, or `None` at the end marker
+  fn decode(
+    &mut self,
+    iter: &mut impl Iterator<Item = bool>,
+  ) -> Result<Option<u8>, ArithmeticError> {
+    match self.tree.decode(iter)? {
+      END => Ok(None),
+      symbol => Ok(Some(symbol as u8)),
+    }
+  }
+}
+
+impl Transform<bool> for AdaptiveHuffmanDecoder {
+  type Out = Result<u8, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = bool>) -> Option<Self::Out> {
+    if self.ended {
+      return None;
+    }
+
+    let decoded = self.decode(iter);
+    end_after(&mut self.ended, decoded)
+  }
+}
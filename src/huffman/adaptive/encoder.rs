@@ -0,0 +1,43 @@
+use super::{AdaptiveHuffmanCompressor, FgkTree};
+use crate::{huffman::table::END, ArithmeticError, Transform};
+use std::collections::VecDeque;
+
+/// Adaptive Huffman Encoding algorithm
+#[derive(Default)]
+pub struct AdaptiveHuffmanEncoder {
+  tree: FgkTree,
+  /// Bits of the current code, ready to be output
+  output: VecDeque<bool>,
+  ended: bool,
+}
+
+impl From<AdaptiveHuffmanCompressor> for AdaptiveHuffmanEncoder {
+  fn from(_: AdaptiveHuffmanCompressor) -> Self {
+    Self::default()
+  }
+}
+
+impl Transform<u8> for AdaptiveHuffmanEncoder {
+  type Out = Result<bool, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    loop {
+      if let Some(bit) = self.output.pop_front() {
+        return Some(Ok(bit));
+      }
+
+      if self.ended {
+        return None;
+      }
+
+      // code the next byte or mark the end of the stream
+      let symbol = match iter.next() {
+        Some(byte) => byte as usize,
+        None => {
+          self.ended = true;
+          END
+        }
+      };
+      self.tree.encode(symbol, &mut self.output);
+    }
+  }
+}
@@ -0,0 +1,29 @@
+use crate::{ArithmeticError, Compressor};
+
+mod tree;
+use tree::FgkTree;
+mod encoder;
+pub use encoder::AdaptiveHuffmanEncoder;
+mod decoder;
+pub use decoder::AdaptiveHuffmanDecoder;
+
+/// One pass adaptive Huffman coding.<br>
+/// The encoder and decoder both start from an empty code tree and update it<br>
+/// after every symbol, so there's no header and no need to see the data first.
+///
+/// Symbols are sent in full the first time they're seen, after an escape code.<br>
+/// The stream finishes with an end marker, like `HuffmanCompressor`.
+#[derive(Clone, Copy, Default)]
+pub struct AdaptiveHuffmanCompressor;
+
+unsafe impl Compressor for AdaptiveHuffmanCompressor {
+  type Error = ArithmeticError;
+  type Item = u8;
+  type Data = bool;
+
+  type Encoder = AdaptiveHuffmanEncoder;
+  type Decoder = AdaptiveHuffmanDecoder;
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,67 @@
+use crate::{
+  test::CompressorTests, ArithmeticCompressor, ArithmeticError, Compressor, IteratorTransforms,
+};
+use proptest::proptest;
+
+use super::AdaptiveHuffmanCompressor;
+
+proptest! {
+  #[test]
+  fn test_encode_decode(items: Vec<u8>) {
+    AdaptiveHuffmanCompressor.test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_repetitive_encode_decode(items: Vec<u8>, repeats in 1..16usize) {
+    AdaptiveHuffmanCompressor
+      .test_encode_decode(items.repeat(repeats))
+      .unwrap()
+  }
+}
+
+#[test]
+fn test_close_to_adaptive_arithmetic() {
+  let text = "the quick brown fox jumps over the lazy dog. ".repeat(64);
+  let huffman = (text.bytes())
+    .apply(AdaptiveHuffmanCompressor.encoder())
+    .count();
+  let arithmetic = (text.bytes())
+    .apply(ArithmeticCompressor::adaptive().encoder())
+    .count();
+  assert!(
+    huffman < arithmetic * 11 / 10,
+    "{huffman} bits vs {arithmetic} bits"
+  );
+}
+
+#[test]
+fn test_compresses_repeats() {
+  let encoded = [0u8; 1024]
+    .into_iter()
+    .apply(AdaptiveHuffmanCompressor.encoder())
+    .count();
+  assert!(encoded < 1100, "{encoded} bits");
+}
+
+#[test]
+fn test_truncated() {
+  AdaptiveHuffmanCompressor.test_truncated(Vec::new(), 0, ArithmeticError::Truncated);
+  AdaptiveHuffmanCompressor.test_truncated((0..=255).collect(), 512, ArithmeticError::Truncated);
+}
+
+#[test]
+fn test_repeated_escape() {
+  // `0` sent in full, then escaped again rather than using its own code
+  let bits = [false; 9].into_iter().chain([false]).chain([false; 9]);
+  let decoded = bits
+    .apply(AdaptiveHuffmanCompressor.decoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(decoded, Err(ArithmeticError::OutOfRange));
+}
+
+proptest! {
+  #[test]
+  fn test_decode_arbitrary(bits: Vec<bool>) {
+    AdaptiveHuffmanCompressor.test_decode_arbitrary(bits)
+  }
+}
@@ -0,0 +1,169 @@
+use crate::{huffman::table::SYMBOLS, ArithmeticError, Code};
+
+/// Bits used to send a symbol the first time it's seen
+const SYMBOL_BITS: u32 = usize::BITS - (SYMBOLS - 1).leading_zeros();
+/// Two nodes for each symbol, plus the root
+const NODES: usize = 2 * SYMBOLS + 1;
+
+/// A node, at a position in the sibling property order
+#[derive(Clone, Copy)]
+struct Node {
+  weight: Code,
+  /// The position of the parent, which stays with the position on swaps
+  parent: usize,
+  /// The positions of the left and right children of internal nodes
+  children: Option<[usize; 2]>,
+  /// The symbol of leaves, or `None` for internal nodes and `nyt`
+  symbol: Option<usize>,
+}
+
+/// A Huffman tree updated after every symbol with the FGK algorithm,<br>
+/// see Knuth's [paper](https://doi.org/10.1016/0196-6774(85)90036-7) for details.
+///
+/// Nodes are kept in order of weight, with siblings next to each other,<br>
+/// so the tree can be kept optimal by swapping nodes with the highest<br>
+/// positioned node of the same weight before incrementing them.
+#[derive(Clone)]
+pub(crate) struct FgkTree {
+  nodes: [Node; NODES],
+  /// The position of each symbol's leaf, if it's been seen
+  leaves: [Option<usize>; SYMBOLS],
+  /// The position of the zero weight leaf for symbols not yet transmitted
+  nyt: usize,
+}
+
+impl Default for FgkTree {
+  fn default() -> Self {
+    let root = Node {
+      weight: 0,
+      parent: NODES,
+      children: None,
+      symbol: None,
+    };
+    Self {
+      nodes: [root; NODES],
+      leaves: [None; SYMBOLS],
+      nyt: NODES - 1,
+    }
+  }
+}
+
+impl FgkTree {
+  /// Appends the code for `symbol` to `bits`, then updates the tree
+  pub fn encode(&mut self, symbol: usize, bits: &mut impl Extend<bool>) {
+    let leaf = self.leaves[symbol].unwrap_or(self.nyt);
+
+    // walk up to the root, then output the path down
+    let mut path = Vec::new();
+    let mut node = leaf;
+    while node != NODES - 1 {
+      let parent = self.nodes[node].parent;
+      let is_right = (self.nodes[parent].children).is_some_and(|[_, right]| right == node);
+      path.push(is_right);
+      node = parent;
+    }
+    bits.extend(path.into_iter().rev());
+
+    // new symbols are sent in full after the escape
+    if self.leaves[symbol].is_none() {
+      bits.extend((0..SYMBOL_BITS).rev().map(|i| (symbol >> i) & 1 == 1));
+    }
+    self.update(symbol);
+  }
+
+  /// Reads a code, returning its symbol after updating the tree
+  pub fn decode(
+    &mut self,
+    iter: &mut impl Iterator<Item = bool>,
+  ) -> Result<usize, ArithmeticError> {
+    let mut next_bit = || iter.next().ok_or(ArithmeticError::Truncated);
+
+    let mut node = NODES - 1;
+    while let Some(children) = self.nodes[node].children {
+      node = children[next_bit()? as usize];
+    }
+
+    let symbol = match self.nodes[node].symbol {
+      Some(symbol) => symbol,
+      None => {
+        let mut symbol = 0;
+        for _ in 0..SYMBOL_BITS {
+          symbol = (symbol << 1) | next_bit()? as usize;
+        }
+        // an escaped symbol must be new
+        if (symbol >= SYMBOLS) || self.leaves[symbol].is_some() {
+          return Err(ArithmeticError::OutOfRange);
+        }
+        symbol
+      }
+    };
+    self.update(symbol);
+    Ok(symbol)
+  }
+
+  /// Swaps the subtrees at positions `a` and `b`
+  fn swap(&mut self, a: usize, b: usize) {
+    let (parent_a, parent_b) = (self.nodes[a].parent, self.nodes[b].parent);
+    self.nodes.swap(a, b);
+    (self.nodes[a].parent, self.nodes[b].parent) = (parent_a, parent_b);
+
+    for position in [a, b] {
+      let node = self.nodes[position];
+      for child in node.children.into_iter().flatten() {
+        self.nodes[child].parent = position;
+      }
+      match node.symbol {
+        Some(symbol) => self.leaves[symbol] = Some(position),
+        None if node.children.is_none() => self.nyt = position,
+        None => {}
+      }
+    }
+  }
+
+  /// Increments the weight of `symbol`, adding a leaf for it if it's new
+  fn update(&mut self, symbol: usize) {
+    let mut node = match self.leaves[symbol] {
+      Some(leaf) => leaf,
+      None => {
+        // split the escape leaf into a new escape leaf and the symbol's leaf
+        let parent = self.nyt;
+        let (nyt, leaf) = (parent - 2, parent - 1);
+        self.nodes[parent].children = Some([nyt, leaf]);
+        self.nodes[nyt] = Node {
+          weight: 0,
+          parent,
+          children: None,
+          symbol: None,
+        };
+        self.nodes[leaf] = Node {
+          weight: 0,
+          parent,
+          children: None,
+          symbol: Some(symbol),
+        };
+        (self.nyt, self.leaves[symbol]) = (nyt, Some(leaf));
+        leaf
+      }
+    };
+
+    loop {
+      // move to the highest position of the same weight, unless that's
+      // the parent, then the increment can't break the ordering
+      let weight = self.nodes[node].weight;
+      let mut leader = node;
+      while (leader + 1 < NODES) && (self.nodes[leader + 1].weight == weight) {
+        leader += 1;
+      }
+      if (leader != node) && (leader != self.nodes[node].parent) {
+        self.swap(node, leader);
+        node = leader;
+      }
+
+      self.nodes[node].weight += 1;
+      if node == NODES - 1 {
+        break;
+      }
+      node = self.nodes[node].parent;
+    }
+  }
+}
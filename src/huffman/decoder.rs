@@ -0,0 +1,53 @@
+use super::{
+  table::{HuffmanTable, END},
+  HuffmanCompressor,
+};
+use crate::{utils::end_after, ArithmeticError, FreqHeader, Transform};
+
+/// Huffman Decoding algorithm
+pub struct HuffmanDecoder {
+  table: HuffmanTable,
+  /// Whether the table should be read from the stream first
+  header: bool,
+  ended: bool,
+}
+
+impl From<HuffmanCompressor> for HuffmanDecoder {
+  fn from(value: HuffmanCompressor) -> Self {
+    Self {
+      table: value.table,
+      header: value.header,
+      ended: false,
+    }
+  }
+}
+
+impl HuffmanDecoder {
+  /// Decodes the next byte, or `None` at the end marker
+  fn decode(
+    &mut self,
+    iter: &mut impl Iterator<Item = bool>,
+  ) -> Result<Option<u8>, ArithmeticError> {
+    if self.header {
+      self.header = false;
+      self.table = HuffmanTable::read_header(iter).ok_or(ArithmeticError::InvalidHeader)?;
+    }
+
+    match self.table.decode(iter).ok_or(ArithmeticError::Truncated)? {
+      END => Ok(None),
+      symbol => Ok(Some(symbol as u8)),
+    }
+  }
+}
+
+impl Transform<bool> for HuffmanDecoder {
+  type Out = Result<u8, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = bool>) -> Option<Self::Out> {
+    if self.ended {
+      return None;
+    }
+
+    let decoded = self.decode(iter);
+    end_after(&mut self.ended, decoded)
+  }
+}
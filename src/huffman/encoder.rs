@@ -0,0 +1,63 @@
+use super::{
+  table::{HuffmanTable, END},
+  HuffmanCompressor,
+};
+use crate::{ArithmeticError, FreqHeader, Transform};
+use std::collections::VecDeque;
+
+/// Huffman Encoding algorithm
+pub struct HuffmanEncoder {
+  table: HuffmanTable,
+  /// Bits of the header and the current code, ready to be output
+  output: VecDeque<bool>,
+  ended: bool,
+  failed: bool,
+}
+
+impl From<HuffmanCompressor> for HuffmanEncoder {
+  fn from(value: HuffmanCompressor) -> Self {
+    let mut header = Vec::new();
+    if value.header {
+      value.table.write_header(&mut header);
+    }
+
+    Self {
+      table: value.table,
+      output: header.into(),
+      ended: false,
+      failed: false,
+    }
+  }
+}
+
+impl Transform<u8> for HuffmanEncoder {
+  type Out = Result<bool, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    if self.failed {
+      return None;
+    }
+
+    loop {
+      if let Some(bit) = self.output.pop_front() {
+        return Some(Ok(bit));
+      }
+
+      if self.ended {
+        return None;
+      }
+
+      // code the next byte or mark the end of the stream
+      let symbol = match iter.next() {
+        Some(byte) => byte as usize,
+        None => {
+          self.ended = true;
+          END
+        }
+      };
+      if self.table.encode(symbol, &mut self.output).is_none() {
+        self.failed = true;
+        return Some(Err(ArithmeticError::ZeroFrequency));
+      }
+    }
+  }
+}
@@ -0,0 +1,67 @@
+use crate::{ArithmeticError, Compressor, FreqTable};
+
+mod adaptive;
+pub use adaptive::{AdaptiveHuffmanCompressor, AdaptiveHuffmanDecoder, AdaptiveHuffmanEncoder};
+mod package_merge;
+mod table;
+pub use table::HuffmanTable;
+mod encoder;
+pub use encoder::HuffmanEncoder;
+mod decoder;
+pub use decoder::HuffmanDecoder;
+
+/// Huffman coding with canonical codes.<br>
+/// Each byte is replaced by its code from a `HuffmanTable`, and the<br>
+/// stream finishes with the end marker's code, like `ArithmeticCompressor`.
+///
+/// This is faster and simpler than arithmetic coding, but every<br>
+/// symbol costs a whole number of bits.
+#[derive(Clone)]
+pub struct HuffmanCompressor {
+  table: HuffmanTable,
+  header: bool,
+}
+
+impl Default for HuffmanCompressor {
+  fn default() -> Self {
+    Self::new(HuffmanTable::default())
+  }
+}
+
+impl HuffmanCompressor {
+  /// A Huffman compressor coding with the codes in `table`
+  pub fn new(table: HuffmanTable) -> Self {
+    Self {
+      table,
+      header: false,
+    }
+  }
+
+  /// A compressor coding with optimal codes for the bytes of `items`,<br>
+  /// writing its code lengths ahead of the coded bits.
+  pub fn from_sample(items: impl IntoIterator<Item = u8>) -> Self {
+    let frequencies = FreqTable::from_sample(items);
+    Self::new(HuffmanTable::new(&frequencies)).with_header()
+  }
+
+  /// Writes the code lengths ahead of the coded bits when encoding,<br>
+  /// and reads them back in place of the decoder's own table.
+  pub fn with_header(self) -> Self {
+    Self {
+      header: true,
+      ..self
+    }
+  }
+}
+
+unsafe impl Compressor for HuffmanCompressor {
+  type Error = ArithmeticError;
+  type Item = u8;
+  type Data = bool;
+
+  type Encoder = HuffmanEncoder;
+  type Decoder = HuffmanDecoder;
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,50 @@
+use crate::Code;
+
+/// An item in the package-merge lists: a total weight,<br>
+/// along with the leaves it's made up of.
+#[derive(Clone)]
+struct Package {
+  weight: Code,
+  symbols: Vec<usize>,
+}
+
+/// Optimal code lengths for `weights` of at most `max_length` bits,<br>
+/// using Larmore & Hirschberg's [package-merge](https://doi.org/10.1145/79147.79150) algorithm.<br>
+/// Symbols with a weight of zero get a length of zero.
+///
+/// There must be at least two symbols with non-zero weights,<br>
+/// and no more than `1 << max_length` of them.
+pub(crate) fn limited_lengths(weights: &[Code], max_length: u32) -> Vec<u32> {
+  let mut leaves: Vec<_> = (weights.iter().enumerate())
+    .filter(|(_, weight)| **weight > 0)
+    .map(|(symbol, weight)| Package {
+      weight: *weight,
+      symbols: vec![symbol],
+    })
+    .collect();
+  leaves.sort_by_key(|leaf| leaf.weight);
+
+  // each round packages pairs of the previous list, merging the
+  // packages with the leaves to form the list one level shallower
+  let mut list: Vec<Package> = Vec::new();
+  for _ in 0..max_length {
+    let packages = list.chunks_exact(2).map(|pair| Package {
+      weight: pair[0].weight + pair[1].weight,
+      symbols: [&pair[0].symbols[..], &pair[1].symbols[..]].concat(),
+    });
+
+    // stable, so leaves come before packages of the same weight
+    list = leaves.iter().cloned().chain(packages).collect();
+    list.sort_by_key(|item| item.weight);
+  }
+
+  // a symbol's length is the number of times it appears
+  // in the `2n - 2` lightest items of the final list
+  let mut lengths = vec![0; weights.len()];
+  for package in list.iter().take(2 * leaves.len() - 2) {
+    for &symbol in &package.symbols {
+      lengths[symbol] += 1;
+    }
+  }
+  lengths
+}
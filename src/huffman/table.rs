@@ -0,0 +1,215 @@
+use super::package_merge::limited_lengths;
+use crate::{
+  arithmetic::{read_gamma, write_gamma},
+  Code, FreqHeader, FreqTable, SymbolFreq,
+};
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// The number of symbols: every byte, then the end of stream marker
+pub(crate) const SYMBOLS: usize = 257;
+/// The symbol coded after the last byte
+pub(crate) const END: usize = 256;
+
+/// A canonical prefix code over bytes and an end of stream marker.<br>
+/// Codes are assigned in order of length, then symbol, so the<br>
+/// code lengths alone are enough to rebuild the table.
+#[derive(Clone)]
+pub struct HuffmanTable {
+  lengths: Vec<u32>,
+  /// Each symbol's code, read most significant bit first
+  codes: Vec<Code>,
+  /// The number of codes of each length
+  counts: Vec<Code>,
+  /// Symbols with codes, in order of their codes
+  sorted: Vec<usize>,
+}
+
+impl Default for HuffmanTable {
+  fn default() -> Self {
+    Self::new(&FreqTable::default())
+  }
+}
+
+impl HuffmanTable {
+  /// An optimal prefix code for the frequencies in `frequencies`,<br>
+  /// including its end marker. Bytes with a frequency of zero get no code.
+  pub fn new(frequencies: &FreqTable) -> Self {
+    let weights = Self::weights(frequencies);
+
+    // repeatedly merge the two lightest trees, ties broken by creation order
+    // so the same frequencies always give the same code
+    let mut heap: BinaryHeap<_> = (weights.iter().enumerate())
+      .filter(|(_, weight)| **weight > 0)
+      .map(|(symbol, weight)| Reverse((*weight, symbol)))
+      .collect();
+    let mut parents = vec![usize::MAX; SYMBOLS];
+    while heap.len() > 1 {
+      let (Some(Reverse((w1, a))), Some(Reverse((w2, b)))) = (heap.pop(), heap.pop()) else {
+        unreachable!("the heap holds at least two trees")
+      };
+      let node = parents.len();
+      parents.push(usize::MAX);
+      (parents[a], parents[b]) = (node, node);
+      heap.push(Reverse((w1 + w2, node)));
+    }
+
+    // a symbol's code length is its depth in the tree
+    let mut lengths = vec![0; SYMBOLS];
+    for (symbol, length) in lengths.iter_mut().enumerate() {
+      if weights[symbol] == 0 {
+        continue;
+      }
+      let mut node = symbol;
+      while parents[node] != usize::MAX {
+        node = parents[node];
+        *length += 1;
+      }
+    }
+
+    // skewed enough frequencies make trees deeper than codes can be
+    if lengths.iter().any(|length| *length > Code::BITS) {
+      return Self::length_limited(frequencies, Code::BITS);
+    }
+    let Some(table) = Self::from_lengths(&lengths) else {
+      unreachable!("huffman codes are complete")
+    };
+    table
+  }
+
+  /// An optimal prefix code for `frequencies` with no code longer than<br>
+  /// `max_length` bits, as formats like Deflate require.
+  ///
+  /// ## Panics
+  ///
+  /// If `max_length` is more than `Code::BITS`, or too short<br>
+  /// to give every symbol with a non-zero frequency a code.
+  pub fn length_limited(frequencies: &FreqTable, max_length: u32) -> Self {
+    let weights = Self::weights(frequencies);
+    let symbols = weights.iter().filter(|weight| **weight > 0).count();
+    assert!(
+      max_length <= Code::BITS,
+      "codes can't be longer than Code::BITS"
+    );
+    assert!(
+      (symbols as u128) <= 1 << max_length,
+      "{symbols} symbols don't fit in codes of {max_length} bits"
+    );
+
+    let Some(table) = Self::from_lengths(&limited_lengths(&weights, max_length)) else {
+      unreachable!("package-merge codes are complete")
+    };
+    table
+  }
+
+  /// Each byte's frequency in `frequencies`, then the end marker's.<br>
+  /// Codes need two symbols, so an empty table still codes byte `0`.
+  fn weights(frequencies: &FreqTable) -> Vec<Code> {
+    let range_len = |(low, high): (Code, Code)| high - low;
+    let mut weights: Vec<_> = (0..=255)
+      .map(|byte| range_len(frequencies.range(byte)))
+      .chain([range_len(frequencies.end())])
+      .collect();
+    if weights[..END].iter().all(|weight| *weight == 0) {
+      weights[0] = 1;
+    }
+    weights
+  }
+
+  /// A canonical code with each symbol's code length, `0` for no code.<br>
+  /// Returns `None` unless the lengths describe a complete prefix code<br>
+  /// with codes for at least two symbols and none longer than `Code::BITS`.
+  pub fn from_lengths(lengths: &[u32]) -> Option<Self> {
+    if lengths.len() != SYMBOLS {
+      return None;
+    }
+    let max_length = *lengths.iter().max()?;
+    if max_length > Code::BITS {
+      return None;
+    }
+
+    // the kraft sum of a complete code is exactly one
+    let kraft: u128 = (lengths.iter())
+      .filter(|length| **length > 0)
+      .map(|length| 1u128 << (Code::BITS - length))
+      .sum();
+    if kraft != 1 << Code::BITS {
+      return None;
+    }
+
+    let mut sorted: Vec<usize> = (0..SYMBOLS).filter(|s| lengths[*s] > 0).collect();
+    sorted.sort_by_key(|symbol| lengths[*symbol]);
+    if sorted.len() < 2 {
+      return None;
+    }
+
+    let mut counts = vec![0; max_length as usize + 1];
+    let mut codes = vec![0; SYMBOLS];
+    let (mut code, mut previous): (Code, u32) = (0, 0);
+    for &symbol in &sorted {
+      let length = lengths[symbol];
+      counts[length as usize] += 1;
+      code <<= length - previous;
+      codes[symbol] = code;
+      code = code.wrapping_add(1);
+      previous = length;
+    }
+
+    Some(Self {
+      lengths: lengths.to_vec(),
+      codes,
+      counts,
+      sorted,
+    })
+  }
+
+  /// Each symbol's code length, bytes first then the end marker
+  pub fn lengths(&self) -> &[u32] {
+    &self.lengths
+  }
+
+  /// Appends the code for `symbol` to `bits`,<br>
+  /// returning `None` if the symbol has no code.
+  pub(crate) fn encode(&self, symbol: usize, bits: &mut impl Extend<bool>) -> Option<()> {
+    let (code, length) = (self.codes[symbol], self.lengths[symbol]);
+    if length == 0 {
+      return None;
+    }
+    bits.extend((0..length).rev().map(|i| (code >> i) & 1 == 1));
+    Some(())
+  }
+
+  /// Reads a code, returning its symbol or `None` if the bits run out
+  pub(crate) fn decode(&self, iter: &mut impl Iterator<Item = bool>) -> Option<usize> {
+    // codes of each length are consecutive, starting at `first`
+    let (mut code, mut first, mut index): (Code, Code, Code) = (0, 0, 0);
+    for &count in &self.counts[1..] {
+      code |= Code::from(iter.next()?);
+      if code - first < count {
+        return Some(self.sorted[(index + (code - first)) as usize]);
+      }
+      index += count;
+      first = first.wrapping_add(count) << 1;
+      code <<= 1;
+    }
+    unreachable!("complete codes decode every sequence of bits")
+  }
+}
+
+/// Each symbol's code length as an Elias gamma code
+impl FreqHeader for HuffmanTable {
+  fn write_header(&self, bits: &mut Vec<bool>) {
+    for length in &self.lengths {
+      write_gamma(Code::from(*length) + 1, bits);
+    }
+  }
+
+  fn read_header(bits: &mut dyn Iterator<Item = bool>) -> Option<Self> {
+    let lengths = (0..SYMBOLS)
+      .map(|_| {
+        let length = read_gamma(bits)? - 1;
+        u32::try_from(length).ok()
+      })
+      .collect::<Option<Vec<_>>>()?;
+    Self::from_lengths(&lengths)
+  }
+}
@@ -0,0 +1,205 @@
+use crate::{
+  test::{skewed_bytes, CompressorTests},
+  ArithmeticCompressor, ArithmeticError, Code, Compressor, FreqHeader, FreqTable,
+  IteratorTransforms, SymbolFreq,
+};
+use proptest::{prop_assert, prop_assert_eq, proptest};
+
+use super::{HuffmanCompressor, HuffmanTable};
+
+proptest! {
+  #[test]
+  fn test_encode_decode(items: Vec<u8>) {
+    HuffmanCompressor::default().test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_from_sample_encode_decode(items: Vec<u8>) {
+    HuffmanCompressor::from_sample(items.clone())
+      .test_encode_decode(items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_header_decode_without_sample(items: Vec<u8>) {
+    HuffmanCompressor::from_sample(items.clone())
+      .test_header_decode_without_sample(HuffmanCompressor::default().with_header(), items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_header_round_trip(items: Vec<u8>) {
+    let table = HuffmanTable::new(&FreqTable::from_sample(items));
+    let mut bits = Vec::new();
+    table.write_header(&mut bits);
+    let read = HuffmanTable::read_header(&mut bits.into_iter()).unwrap();
+    prop_assert_eq!(table.lengths(), read.lengths());
+  }
+}
+
+#[test]
+fn test_code_lengths() {
+  // frequencies 1, 2, 4, ... give lengths n, n - 1, ..., 1
+  let mut counts = [0; 256];
+  for (i, count) in counts.iter_mut().take(8).enumerate() {
+    *count = 1 << i;
+  }
+  let table = HuffmanTable::new(&FreqTable::from_counts(counts));
+
+  // the end marker has a frequency of one, like the first byte
+  let lengths = table.lengths();
+  assert_eq!(&lengths[..8], &[8, 7, 6, 5, 4, 3, 2, 1][..]);
+  assert!(lengths[8..256].iter().all(|length| *length == 0));
+  assert_eq!(lengths[256], 8);
+}
+
+#[test]
+fn test_from_lengths_rejects_incomplete() {
+  let mut lengths = vec![0; 257];
+  lengths[0] = 1;
+  assert!(HuffmanTable::from_lengths(&lengths).is_none());
+
+  lengths[1] = 2;
+  assert!(HuffmanTable::from_lengths(&lengths).is_none());
+
+  lengths[2] = 2;
+  assert!(HuffmanTable::from_lengths(&lengths).is_some());
+}
+
+/// Frequencies following the fibonacci sequence on from the end marker's<br>
+/// frequency of one, giving the deepest codes
+fn fibonacci_frequencies(n: usize) -> FreqTable {
+  let mut counts = [0; 256];
+  let (mut a, mut b) = (1, 2);
+  for count in counts.iter_mut().take(n) {
+    *count = a;
+    (a, b) = (b, a + b);
+  }
+  FreqTable::from_counts(counts)
+}
+
+/// The total bits spent coding `frequencies` with `table`
+fn cost(table: &HuffmanTable, frequencies: &FreqTable) -> Code {
+  (0..=255u8)
+    .map(|byte| {
+      let (low, high) = frequencies.range(byte);
+      (high - low) * Code::from(table.lengths()[byte as usize])
+    })
+    .sum()
+}
+
+#[test]
+fn test_length_limited_fibonacci() {
+  let frequencies = fibonacci_frequencies(30);
+  let unlimited = HuffmanTable::new(&frequencies);
+  assert!(unlimited.lengths().iter().any(|length| *length > 15));
+
+  let limited = HuffmanTable::length_limited(&frequencies, 15);
+  assert!(limited.lengths().iter().all(|length| *length <= 15));
+  assert!(cost(&limited, &frequencies) >= cost(&unlimited, &frequencies));
+}
+
+#[test]
+fn test_deeper_than_code_bits() {
+  // a Huffman tree over these is deeper than a code can be long
+  let table = HuffmanTable::new(&fibonacci_frequencies(80));
+  assert!(table.lengths().iter().all(|length| *length <= Code::BITS));
+  HuffmanCompressor::new(table)
+    .test_encode_decode((0..80).collect())
+    .unwrap();
+}
+
+#[test]
+fn test_length_limited_to_fixed_length() {
+  // huffman would give these 40 bytes and the end marker codes of up to 40 bits
+  let table = HuffmanTable::length_limited(&fibonacci_frequencies(40), 6);
+  assert!(table.lengths().iter().all(|length| *length <= 6));
+}
+
+proptest! {
+  #[test]
+  fn test_length_limited_matches_huffman(items: Vec<u8>) {
+    // a limit that never binds gives codes just as good
+    let frequencies = FreqTable::from_sample(items);
+    let unlimited = HuffmanTable::new(&frequencies);
+    let limited = HuffmanTable::length_limited(&frequencies, Code::BITS);
+    prop_assert_eq!(cost(&limited, &frequencies), cost(&unlimited, &frequencies));
+  }
+
+  #[test]
+  fn test_length_limited_encode_decode(items: Vec<u8>, max_length in 9..16u32) {
+    let table = HuffmanTable::length_limited(&FreqTable::from_sample(items.clone()), max_length);
+    prop_assert!(table.lengths().iter().all(|length| *length <= max_length));
+    HuffmanCompressor::new(table)
+      .with_header()
+      .test_encode_decode(items)
+      .unwrap()
+  }
+}
+
+#[test]
+fn test_close_to_arithmetic() {
+  let items = skewed_bytes();
+  let frequencies = FreqTable::from_sample(items.clone());
+  let huffman = (items.iter().copied())
+    .apply(HuffmanCompressor::new(HuffmanTable::new(&frequencies)).encoder())
+    .count();
+  let arithmetic = (items.iter().copied())
+    .apply(ArithmeticCompressor::new(frequencies).encoder())
+    .count();
+  assert!(
+    huffman < arithmetic * 11 / 10,
+    "{huffman} bits vs {arithmetic} bits"
+  );
+}
+
+#[test]
+fn test_zero_frequency_item() {
+  let mut counts = [1; 256];
+  counts[1] = 0;
+  let table = HuffmanTable::new(&FreqTable::from_counts(counts));
+  HuffmanCompressor::new(table).test_encode_error(vec![0, 1], ArithmeticError::ZeroFrequency);
+}
+
+#[test]
+fn test_truncated() {
+  let compressor = HuffmanCompressor::default();
+  (compressor.clone()).test_truncated(Vec::new(), 0, ArithmeticError::Truncated);
+  compressor.test_truncated((0..=255).collect(), 512, ArithmeticError::Truncated);
+}
+
+#[test]
+fn test_all_zero_counts() {
+  // only the end marker has a frequency, but codes need two symbols
+  let table = HuffmanTable::new(&FreqTable::from_counts([0; 256]));
+  HuffmanCompressor::new(table)
+    .test_encode_decode(Vec::new())
+    .unwrap();
+  HuffmanCompressor::from_sample([])
+    .test_encode_decode(Vec::new())
+    .unwrap();
+}
+
+#[test]
+fn test_invalid_header() {
+  // every code length of zero
+  let decoded = [true; 257]
+    .into_iter()
+    .apply(HuffmanCompressor::default().with_header().decoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(decoded, Err(ArithmeticError::InvalidHeader));
+}
+
+proptest! {
+  #[test]
+  fn test_decode_arbitrary(bits: Vec<bool>) {
+    HuffmanCompressor::default().test_decode_arbitrary(bits)
+  }
+
+  #[test]
+  fn test_header_decode_arbitrary(bits: Vec<bool>) {
+    HuffmanCompressor::default()
+      .with_header()
+      .test_decode_arbitrary(bits)
+  }
+}
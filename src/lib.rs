@@ -8,9 +8,44 @@
 //! - `Compressor`: An algorithm that provides both a method to encode an
 //!   iterator and a method to decode the iterator produced.
 mod arithmetic;
-pub use arithmetic::ArithmeticEncoder;
+pub use arithmetic::{
+    freq_max, ArithmeticCompressor, ArithmeticDecoder, ArithmeticEncoder, ArithmeticError, Code,
+    FenwickTable, FreqHeader, FreqTable, Symbol, SymbolFreq, FREQ_MAX, MAX_PRECISION, PRECISION,
+};
 mod bits;
 pub use bits::{FromBits, IntFromBits, IntIntoBits, IntoBits};
+mod bwt;
+pub use bwt::{Bwt, BwtDecoder, BwtEncoder, BwtError};
+mod fse;
+pub use fse::{FseCompressor, FseDecoder, FseEncoder, FseTable};
+mod huffman;
+pub use huffman::{
+    AdaptiveHuffmanCompressor, AdaptiveHuffmanDecoder, AdaptiveHuffmanEncoder, HuffmanCompressor,
+    HuffmanDecoder, HuffmanEncoder, HuffmanTable,
+};
+mod lz77;
+pub use lz77::{Lz77Compressor, Lz77Decoder, Lz77Encoder, Lz77Error, Lz77Token};
+mod lzw;
+pub use lzw::{LzwCompressor, LzwDecoder, LzwEncoder, LzwError, LzwPolicy};
+mod match_finder;
+pub use match_finder::{
+    BinaryTree, BuildMatchFinder, HashChain, Match, MatchFinder, MatchFinderKind, MatchWindow,
+};
+mod mtf;
+pub use mtf::{Mtf, MtfDecoder, MtfEncoder, MtfVariant};
+mod ppm;
+pub use ppm::{PpmCompressor, PpmDecoder, PpmEncoder};
+mod range;
+pub use range::{RangeCompressor, RangeDecoder, RangeEncoder};
+mod rans;
+pub use rans::{RansCompressor, RansDecoder, RansEncoder};
+mod rle;
+pub use rle::{
+    BitRleCompressor, BitRleDecoder, BitRleEncoder, RleCompressor, RleDecoder, RleEncoder,
+    RleError, RunCount, MAX_RUN,
+};
+mod suffix_array;
+pub use suffix_array::{lcp_array, suffix_array};
 mod transforms;
 pub use transforms::{IteratorTransforms, Transform};
 mod utils;
@@ -0,0 +1,39 @@
+use crate::SymbolFreq;
+
+/// The bits in an Elias gamma code for `value > 0`
+fn gamma_bits(value: usize) -> f64 {
+  (2 * value.ilog2() + 1) as f64
+}
+
+/// Estimated bit costs of tokens, used for optimal parsing.
+///
+/// Literals cost what a model would spend on them, with a frequency<br>
+/// of one for bytes it can't code. Matches cost Elias gamma codes of<br>
+/// their length and distance, and every token a bit to tell them apart.
+#[derive(Clone, Copy)]
+pub(crate) struct TokenCosts {
+  literals: [f64; 256],
+}
+
+impl TokenCosts {
+  /// Costs with literals coded by `model`
+  pub fn new<F: SymbolFreq<u8>>(model: &F) -> Self {
+    let len = model.len() as f64;
+    let literals = std::array::from_fn(|byte| {
+      let (low, high) = model.range(byte as u8);
+      1.0 + (len / (high - low).max(1) as f64).log2()
+    });
+    Self { literals }
+  }
+
+  /// The estimated bits spent on a literal `byte`
+  pub fn literal(&self, byte: u8) -> f64 {
+    self.literals[byte as usize]
+  }
+
+  /// The estimated bits spent on a match, with `length`<br>
+  /// counted from one for the shortest match allowed.
+  pub fn matched(&self, distance: usize, length: usize) -> f64 {
+    1.0 + gamma_bits(length) + gamma_bits(distance)
+  }
+}
@@ -0,0 +1,72 @@
+use super::{Lz77Compressor, Lz77Error, Lz77Token};
+use crate::Transform;
+use std::collections::VecDeque;
+
+/// LZ77 Decoding algorithm
+pub struct Lz77Decoder {
+  window: usize,
+  min_match: usize,
+  max_match: usize,
+  /// The last `window` bytes output
+  history: VecDeque<u8>,
+  /// The distance and bytes left of the match being copied
+  copy: (usize, usize),
+  failed: bool,
+}
+
+impl<B> From<Lz77Compressor<B>> for Lz77Decoder {
+  fn from(value: Lz77Compressor<B>) -> Self {
+    Self {
+      window: value.window,
+      min_match: value.min_match,
+      max_match: value.max_match,
+      history: VecDeque::with_capacity(value.window),
+      copy: (0, 0),
+      failed: false,
+    }
+  }
+}
+
+impl Lz77Decoder {
+  /// Adds `byte` to the history, forgetting bytes outside the window
+  fn push(&mut self, byte: u8) -> u8 {
+    if self.history.len() == self.window {
+      self.history.pop_front();
+    }
+    self.history.push_back(byte);
+    byte
+  }
+}
+
+impl Transform<Lz77Token> for Lz77Decoder {
+  type Out = Result<u8, Lz77Error>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = Lz77Token>) -> Option<Self::Out> {
+    if self.failed {
+      return None;
+    }
+
+    if let (distance, remaining @ 1..) = self.copy {
+      self.copy = (distance, remaining - 1);
+      let byte = self.history[self.history.len() - distance];
+      return Some(Ok(self.push(byte)));
+    }
+
+    match iter.next()? {
+      Lz77Token::Literal(byte) => Some(Ok(self.push(byte))),
+      Lz77Token::Match { distance, length } => {
+        if !(1..=self.history.len()).contains(&distance) {
+          self.failed = true;
+          return Some(Err(Lz77Error::InvalidDistance));
+        }
+        if !(self.min_match..=self.max_match).contains(&length) {
+          self.failed = true;
+          return Some(Err(Lz77Error::InvalidLength));
+        }
+
+        self.copy = (distance, length - 1);
+        let byte = self.history[self.history.len() - distance];
+        Some(Ok(self.push(byte)))
+      }
+    }
+  }
+}
@@ -0,0 +1,124 @@
+use std::collections::VecDeque;
+
+use super::{Lz77Compressor, Lz77Error, Lz77Token, TokenCosts};
+use crate::{BuildMatchFinder, MatchFinder, MatchWindow, Transform};
+
+/// The number of bytes parsed at once by optimal parsing
+const OPTIMAL_BLOCK: usize = 1 << 12;
+
+/// LZ77 Encoding algorithm, searching for matches with `M`
+pub struct Lz77Encoder<M = Box<dyn MatchFinder>> {
+  finder: M,
+  window: MatchWindow,
+  min_match: usize,
+  max_match: usize,
+  costs: Option<TokenCosts>,
+  /// Tokens chosen by optimal parsing, waiting to be output
+  tokens: VecDeque<Lz77Token>,
+}
+
+impl<B: BuildMatchFinder> From<Lz77Compressor<B>> for Lz77Encoder<B::Finder> {
+  fn from(value: Lz77Compressor<B>) -> Self {
+    let finder = (value.finder).build(value.window, value.min_match, value.effort);
+    Self {
+      finder,
+      window: MatchWindow::new(value.window),
+      min_match: value.min_match,
+      max_match: value.max_match,
+      costs: value.costs,
+      tokens: VecDeque::new(),
+    }
+  }
+}
+
+impl<M: MatchFinder> Lz77Encoder<M> {
+  /// Reads bytes until there are `ahead` bytes to be matched, if there are that many
+  fn fill(&mut self, ahead: usize, iter: &mut impl Iterator<Item = u8>) {
+    while self.window.lookahead().len() < ahead {
+      let Some(byte) = iter.next() else { break };
+      self.window.push(byte);
+    }
+  }
+
+  /// Picks the longest match at each position
+  fn greedy(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Lz77Token> {
+    self.fill(self.max_match, iter);
+    let &literal = self.window.lookahead().first()?;
+
+    let found = (self.finder.find(&self.window, self.max_match))
+      .filter(|found| found.length >= self.min_match);
+    self.window.advance();
+    let Some(found) = found else {
+      return Some(Lz77Token::Literal(literal));
+    };
+
+    // the finder still needs to see the positions the match covers
+    for _ in 1..found.length {
+      self.finder.skip(&self.window, self.max_match);
+      self.window.advance();
+    }
+    Some(Lz77Token::Match {
+      distance: found.distance,
+      length: found.length,
+    })
+  }
+
+  /// Queues the cheapest tokens for the next block of bytes.<br>
+  /// Every prefix of the longest match at each position is a candidate,<br>
+  /// and the cheapest path through the block is found by dynamic programming.
+  fn optimal(&mut self, costs: TokenCosts, iter: &mut impl Iterator<Item = u8>) {
+    self.fill(OPTIMAL_BLOCK + self.max_match, iter);
+    let block = self.window.lookahead().len().min(OPTIMAL_BLOCK);
+    let bytes = self.window.lookahead()[..block].to_vec();
+    let matches: Vec<_> = (0..block)
+      .map(|_| {
+        let found = (self.finder.find(&self.window, self.max_match))
+          .filter(|found| found.length >= self.min_match);
+        self.window.advance();
+        found
+      })
+      .collect();
+
+    // the cheapest cost of the first `i` bytes, and the token ending them
+    let mut best = vec![(f64::INFINITY, Lz77Token::Literal(0)); block + 1];
+    best[0].0 = 0.0;
+    for i in 0..block {
+      let cost = best[i].0;
+      let literal = cost + costs.literal(bytes[i]);
+      if literal < best[i + 1].0 {
+        best[i + 1] = (literal, Lz77Token::Literal(bytes[i]));
+      }
+      let Some(found) = matches[i] else { continue };
+      for length in self.min_match..=found.length.min(block - i) {
+        let matched = cost + costs.matched(found.distance, length + 1 - self.min_match);
+        if matched < best[i + length].0 {
+          let distance = found.distance;
+          best[i + length] = (matched, Lz77Token::Match { distance, length });
+        }
+      }
+    }
+
+    let mut end = block;
+    while end > 0 {
+      let token = best[end].1;
+      self.tokens.push_front(token);
+      end -= match token {
+        Lz77Token::Literal(_) => 1,
+        Lz77Token::Match { length, .. } => length,
+      };
+    }
+  }
+}
+
+impl<M: MatchFinder> Transform<u8> for Lz77Encoder<M> {
+  type Out = Result<Lz77Token, Lz77Error>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    let Some(costs) = self.costs else {
+      return self.greedy(iter).map(Ok);
+    };
+    if self.tokens.is_empty() {
+      self.optimal(costs, iter);
+    }
+    self.tokens.pop_front().map(Ok)
+  }
+}
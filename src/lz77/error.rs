@@ -0,0 +1,21 @@
+use std::{error::Error, fmt};
+
+/// Errors produced when decoding LZ77 tokens fails
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Lz77Error {
+  /// A match reached back before the start of the stream or the window
+  InvalidDistance,
+  /// A match was shorter or longer than the compressor allows
+  InvalidLength,
+}
+
+impl fmt::Display for Lz77Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidDistance => write!(f, "match distance is outside the window"),
+      Self::InvalidLength => write!(f, "match length is outside the allowed range"),
+    }
+  }
+}
+
+impl Error for Lz77Error {}
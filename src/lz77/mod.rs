@@ -0,0 +1,122 @@
+use crate::{BuildMatchFinder, Compressor, MatchFinderKind, SymbolFreq};
+
+mod cost;
+use cost::TokenCosts;
+mod error;
+pub use error::Lz77Error;
+mod encoder;
+pub use encoder::Lz77Encoder;
+mod decoder;
+pub use decoder::Lz77Decoder;
+
+/// A token produced by LZ77 encoding
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Lz77Token {
+  /// A single byte, output as is
+  Literal(u8),
+  /// A copy of `length` bytes, starting `distance` bytes back.<br>
+  /// The copy can overlap the bytes it produces, for runs.
+  Match { distance: usize, length: usize },
+}
+
+/// The LZ77 sliding window algorithm.<br>
+/// Bytes are replaced with matches against the previous `window` bytes,<br>
+/// see Ziv & Lempel's [paper](https://doi.org/10.1109/TIT.1977.1055714) for details.
+///
+/// The tokens produced are intended to be entropy coded afterwards.<br>
+/// Matches are searched for with a `MatchFinder`, a `HashChain` by default.<br>
+/// Parsing is greedy unless optimal parsing is turned on.
+#[derive(Clone, Copy)]
+pub struct Lz77Compressor<B = MatchFinderKind> {
+  finder: B,
+  /// The finder's effort, if not its default
+  effort: Option<usize>,
+  window: usize,
+  min_match: usize,
+  max_match: usize,
+  costs: Option<TokenCosts>,
+}
+
+impl Default for Lz77Compressor {
+  fn default() -> Self {
+    Self {
+      finder: MatchFinderKind::HashChain,
+      effort: None,
+      window: 1 << 15,
+      min_match: 3,
+      max_match: 258,
+      costs: None,
+    }
+  }
+}
+
+impl Lz77Compressor {
+  /// Sets how far back matches can reach, `1 << 15` by default
+  pub fn with_window(self, window: usize) -> Self {
+    assert!(window > 0, "window must hold at least one byte");
+    Self { window, ..self }
+  }
+}
+
+impl<B> Lz77Compressor<B> {
+  /// Searches for matches with the finder `finder` builds, which can be<br>
+  /// any `MatchFinder`, or one of this crate's picked by `MatchFinderKind`.<br>
+  /// The finder and its effort trade compression speed for ratio.
+  pub fn with_match_finder<F: BuildMatchFinder>(self, finder: F) -> Lz77Compressor<F> {
+    Lz77Compressor {
+      finder,
+      effort: self.effort,
+      window: self.window,
+      min_match: self.min_match,
+      max_match: self.max_match,
+      costs: self.costs,
+    }
+  }
+
+  /// Sets the most candidates the finder compares per search,<br>
+  /// otherwise the default effort of whichever finder is used.
+  pub fn with_effort(self, effort: usize) -> Self {
+    assert!(effort > 0, "effort must be at least one");
+    Self {
+      effort: Some(effort),
+      ..self
+    }
+  }
+
+  /// Sets the shortest and longest matches, `3` and `258` by default.<br>
+  /// Anything shorter than `min_match` is output as literals.
+  pub fn with_match_lengths(self, min_match: usize, max_match: usize) -> Self {
+    assert!(min_match > 0, "matches must be at least one byte");
+    assert!(
+      min_match <= max_match,
+      "min_match must be at most max_match"
+    );
+    Self {
+      min_match,
+      max_match,
+      ..self
+    }
+  }
+
+  /// Picks tokens to minimise their estimated cost in bits, a slower<br>
+  /// but higher compression level. Literals are costed with `literals`,<br>
+  /// the model they are expected to be entropy coded with.
+  pub fn with_optimal_parsing<F: SymbolFreq<u8>>(self, literals: &F) -> Self {
+    Self {
+      costs: Some(TokenCosts::new(literals)),
+      ..self
+    }
+  }
+}
+
+unsafe impl<B: BuildMatchFinder> Compressor for Lz77Compressor<B> {
+  type Error = Lz77Error;
+  type Item = u8;
+  type Data = Lz77Token;
+
+  type Encoder = Lz77Encoder<B::Finder>;
+  type Decoder = Lz77Decoder;
+}
+
+#[cfg(test)]
+mod test;
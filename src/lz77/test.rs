@@ -0,0 +1,233 @@
+use crate::{
+  test::CompressorTests, BuildMatchFinder, Compressor, FreqTable, IteratorTransforms, Match,
+  MatchFinder, MatchFinderKind, MatchWindow,
+};
+use proptest::{
+  collection::vec,
+  prelude::{any, Strategy},
+  prop_oneof, proptest,
+};
+
+use super::{Lz77Compressor, Lz77Error, Lz77Token, TokenCosts};
+
+/// Tokens with small distances and lengths, so some of them are valid
+fn tokens() -> impl Strategy<Value = Vec<Lz77Token>> {
+  let token = prop_oneof![
+    any::<u8>().prop_map(Lz77Token::Literal),
+    (0..16usize, 0..16usize).prop_map(|(distance, length)| Lz77Token::Match { distance, length }),
+  ];
+  vec(token, 0..64)
+}
+
+proptest! {
+  #[test]
+  fn test_encode_decode(items: Vec<u8>) {
+    Lz77Compressor::default().test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_repetitive_encode_decode(items: Vec<u8>, repeats in 1..16usize) {
+    Lz77Compressor::default()
+      .test_encode_decode(items.repeat(repeats))
+      .unwrap()
+  }
+
+  #[test]
+  fn test_configured_encode_decode(
+    items in vec(0..4u8, 0..512),
+    window in 1..64usize,
+    min_match in 1..8usize,
+    extra in 0..8usize,
+  ) {
+    // a small alphabet and window exercise sliding and overlapping matches
+    Lz77Compressor::default()
+      .with_window(window)
+      .with_match_lengths(min_match, min_match + extra)
+      .test_encode_decode(items)
+      .unwrap()
+  }
+}
+
+#[test]
+fn test_compresses_repeats() {
+  let text = "the quick brown fox jumps over the lazy dog. ".repeat(64);
+  let tokens: Vec<_> = (text.bytes())
+    .apply(Lz77Compressor::default().encoder())
+    .collect::<Result<_, _>>()
+    .unwrap();
+  assert!(tokens.len() < 64, "{} tokens", tokens.len());
+}
+
+#[test]
+fn test_matches_stay_in_window() {
+  let items = (0..4096u32).map(|i| (i % 251) as u8);
+  for token in items.apply(Lz77Compressor::default().with_window(300).encoder()) {
+    if let Lz77Token::Match { distance, length } = token.unwrap() {
+      assert!(distance <= 300, "distance {distance}");
+      assert!((3..=258).contains(&length), "length {length}");
+    }
+  }
+}
+
+#[test]
+fn test_invalid_tokens() {
+  let decode = |tokens: Vec<Lz77Token>| {
+    (tokens.into_iter())
+      .apply(Lz77Compressor::default().with_window(4).decoder())
+      .collect::<Result<Vec<_>, _>>()
+  };
+
+  let literals = vec![Lz77Token::Literal(0); 8];
+  let far = Lz77Token::Match {
+    distance: 5,
+    length: 3,
+  };
+  let short = Lz77Token::Match {
+    distance: 1,
+    length: 2,
+  };
+  assert_eq!(decode(vec![far]), Err(Lz77Error::InvalidDistance));
+  assert_eq!(
+    decode([literals.clone(), vec![far]].concat()),
+    Err(Lz77Error::InvalidDistance)
+  );
+  assert_eq!(
+    decode([literals, vec![short]].concat()),
+    Err(Lz77Error::InvalidLength)
+  );
+}
+
+proptest! {
+  #[test]
+  fn test_decode_arbitrary(tokens in tokens()) {
+    Lz77Compressor::default().test_decode_arbitrary(tokens)
+  }
+}
+
+proptest! {
+  #[test]
+  fn test_binary_tree_encode_decode(items in vec(0..4u8, 0..512), window in 1..64usize) {
+    Lz77Compressor::default()
+      .with_window(window)
+      .with_match_finder(MatchFinderKind::BinaryTree)
+      .test_encode_decode(items)
+      .unwrap()
+  }
+}
+
+#[test]
+fn test_effort_improves_ratio() {
+  // many candidates share each prefix, so low efforts miss the longest
+  let items: Vec<u8> = (0..1 << 14)
+    .map(|i: u32| (i.count_ones() % 4) as u8)
+    .collect();
+  let token_count = |effort: usize| {
+    let compressor = (Lz77Compressor::default())
+      .with_match_finder(MatchFinderKind::BinaryTree)
+      .with_effort(effort);
+    (items.iter().copied()).apply(compressor.encoder()).count()
+  };
+  let (low, high) = (token_count(1), token_count(256));
+  assert!(high < low, "{high} tokens vs {low} tokens");
+}
+
+#[test]
+fn test_short_matches() {
+  // the repeated prefixes are exactly `min_match` bytes long
+  for finder in [MatchFinderKind::HashChain, MatchFinderKind::BinaryTree] {
+    for min_match in 1..=2 {
+      let items = (0..64u8).flat_map(|i| [vec![b'x'; min_match], vec![i]].concat());
+      let compressor = (Lz77Compressor::default())
+        .with_match_finder(finder)
+        .with_match_lengths(min_match, 258);
+      let matches = (items.apply(compressor.encoder()))
+        .filter(
+          |token| matches!(token, Ok(Lz77Token::Match { length, .. }) if *length == min_match),
+        )
+        .count();
+      assert_eq!(matches, 63, "{finder:?} with min_match {min_match}");
+    }
+  }
+}
+
+/// A finder of no matches, built by itself
+#[derive(Clone, Copy)]
+struct NoMatches;
+
+impl MatchFinder for NoMatches {
+  fn find(&mut self, _: &MatchWindow, _: usize) -> Option<Match> {
+    None
+  }
+}
+
+impl BuildMatchFinder for NoMatches {
+  type Finder = Self;
+  fn build(&self, _: usize, _: usize, _: Option<usize>) -> Self {
+    Self
+  }
+}
+
+#[test]
+fn test_custom_match_finder() {
+  let text = "abcabcabc".repeat(8).into_bytes();
+  let compressor = Lz77Compressor::default().with_match_finder(NoMatches);
+  let tokens: Vec<_> = (text.iter().copied())
+    .apply(compressor.encoder())
+    .collect::<Result<_, _>>()
+    .unwrap();
+  assert!(tokens
+    .iter()
+    .all(|token| matches!(token, Lz77Token::Literal(_))));
+  compressor.test_encode_decode(text).unwrap();
+}
+
+/// The estimated bits spent on the tokens for `items`
+fn parse_cost(compressor: Lz77Compressor, costs: &TokenCosts, items: &[u8]) -> f64 {
+  (items.iter().copied())
+    .apply(compressor.encoder())
+    .map(|token| match token.unwrap() {
+      Lz77Token::Literal(byte) => costs.literal(byte),
+      Lz77Token::Match { distance, length } => costs.matched(distance, length - 2),
+    })
+    .sum()
+}
+
+proptest! {
+  #[test]
+  fn test_optimal_encode_decode(items in vec(0..4u8, 0..6000), window in 1..64usize) {
+    let model = FreqTable::from_sample(items.iter().copied());
+    Lz77Compressor::default()
+      .with_window(window)
+      .with_optimal_parsing(&model)
+      .test_encode_decode(items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_optimal_beats_greedy(items in vec(0..4u8, 0..4096)) {
+    // within a single block, the optimal parse is never costlier
+    let model = FreqTable::from_sample(items.iter().copied());
+    let costs = TokenCosts::new(&model);
+    let greedy = parse_cost(Lz77Compressor::default(), &costs, &items);
+    let optimal = parse_cost(
+      Lz77Compressor::default().with_optimal_parsing(&model),
+      &costs,
+      &items,
+    );
+    assert!(optimal <= greedy + 1e-9, "{optimal} bits vs {greedy} bits");
+  }
+}
+
+#[test]
+fn test_optimal_improves_ratio() {
+  let text = "abracadabra, abrasive cadavers abound. ".repeat(32);
+  let model = FreqTable::from_sample(text.bytes());
+  let costs = TokenCosts::new(&model);
+  let greedy = parse_cost(Lz77Compressor::default(), &costs, text.as_bytes());
+  let optimal = parse_cost(
+    Lz77Compressor::default().with_optimal_parsing(&model),
+    &costs,
+    text.as_bytes(),
+  );
+  assert!(optimal < greedy, "{optimal} bits vs {greedy} bits");
+}
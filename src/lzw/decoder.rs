@@ -0,0 +1,75 @@
+use std::collections::VecDeque;
+
+use super::{Dictionary, LzwCompressor, LzwError, END};
+use crate::{utils::end_after, FromBits, Transform};
+
+/// LZW Decoding algorithm
+pub struct LzwDecoder {
+  dictionary: Dictionary,
+  /// The previous code, whose string the next one extends
+  previous: Option<u32>,
+  decoded: VecDeque<u8>,
+  ended: bool,
+}
+
+impl From<LzwCompressor> for LzwDecoder {
+  fn from(value: LzwCompressor) -> Self {
+    Self {
+      dictionary: value.dictionary(),
+      previous: None,
+      decoded: VecDeque::new(),
+      ended: false,
+    }
+  }
+}
+
+impl LzwDecoder {
+  /// Decodes the string for the next code, or `None` at the end code
+  fn decode(&mut self, iter: &mut impl Iterator<Item = bool>) -> Result<Option<()>, LzwError> {
+    // the encoder made room for the previous string to be extended before this code
+    let previous = self.previous;
+    let slot = previous.and_then(|code| self.dictionary.reserve(code));
+    let width = self.dictionary.width();
+    let code = FromBits::<u32>::read(width, iter).ok_or(LzwError::Truncated)?;
+    if code == END {
+      return Ok(None);
+    }
+
+    let bytes = match previous {
+      // the string being added can be used straight away, when it starts with its own first byte
+      Some(previous) if Some(code) == slot => {
+        let mut bytes = self.dictionary.bytes(previous);
+        bytes.push(bytes[0]);
+        bytes
+      }
+      _ if self.dictionary.contains(code) => self.dictionary.bytes(code),
+      _ => return Err(LzwError::InvalidCode),
+    };
+    if let (Some(slot), Some(previous)) = (slot, previous) {
+      self.dictionary.fill(slot, previous, bytes[0]);
+    }
+    self.dictionary.touch(code);
+    self.previous = Some(code);
+    self.decoded.extend(bytes);
+    Ok(Some(()))
+  }
+}
+
+impl Transform<bool> for LzwDecoder {
+  type Out = Result<u8, LzwError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = bool>) -> Option<Self::Out> {
+    loop {
+      if let Some(byte) = self.decoded.pop_front() {
+        return Some(Ok(byte));
+      }
+      if self.ended {
+        return None;
+      }
+
+      let decoded = self.decode(iter);
+      if let Some(Err(e)) = end_after(&mut self.ended, decoded) {
+        return Some(Err(e));
+      }
+    }
+  }
+}
@@ -0,0 +1,150 @@
+use std::collections::{BTreeSet, HashMap};
+
+use super::LzwPolicy;
+
+/// The code marking the end of the stream
+pub(crate) const END: u32 = 256;
+/// The first code given to a string of more than one byte
+const FIRST: u32 = END + 1;
+
+/// A string in the dictionary, one byte longer than its parent
+#[derive(Clone, Copy)]
+struct Entry {
+  parent: Option<u32>,
+  byte: u8,
+  /// The number of entries extending this one
+  children: u32,
+  /// When the entry was last used, for pruning
+  used: u64,
+}
+
+/// The strings shared by the LZW encoder and decoder, as a trie of codes.<br>
+/// Both sides make the same changes in the same order, so they always agree.
+pub(crate) struct Dictionary {
+  entries: Vec<Entry>,
+  lookup: HashMap<(u32, u8), u32>,
+  /// Entries of more than one byte without children, by when they were last used
+  leaves: BTreeSet<(u64, u32)>,
+  clock: u64,
+  capacity: usize,
+  policy: LzwPolicy,
+}
+
+impl Dictionary {
+  /// A dictionary of single bytes, growing to `1 << max_bits` codes
+  pub fn new(max_bits: u32, policy: LzwPolicy) -> Self {
+    let entries = (0..FIRST)
+      .map(|code| Entry {
+        parent: None,
+        byte: code as u8,
+        children: 0,
+        used: 0,
+      })
+      .collect();
+    Self {
+      entries,
+      lookup: HashMap::new(),
+      leaves: BTreeSet::new(),
+      clock: 0,
+      capacity: 1 << max_bits,
+      policy,
+    }
+  }
+
+  /// The number of bits needed for any code currently in use
+  pub fn width(&self) -> u32 {
+    u32::BITS - (self.entries.len() as u32 - 1).leading_zeros()
+  }
+
+  /// Whether `code` has been given out
+  pub fn contains(&self, code: u32) -> bool {
+    (code as usize) < self.entries.len() && code != END
+  }
+
+  /// The code for `code`'s string followed by `byte`
+  pub fn child(&self, code: u32, byte: u8) -> Option<u32> {
+    self.lookup.get(&(code, byte)).copied()
+  }
+
+  /// The bytes of the string for `code`, which must be in the dictionary
+  pub fn bytes(&self, code: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut code = Some(code);
+    while let Some(entry) = code.map(|code| self.entries[code as usize]) {
+      bytes.push(entry.byte);
+      code = entry.parent;
+    }
+    bytes.reverse();
+    bytes
+  }
+
+  /// Marks `code` as just used
+  pub fn touch(&mut self, code: u32) {
+    self.clock += 1;
+    let entry = &mut self.entries[code as usize];
+    if code >= FIRST && entry.children == 0 {
+      self.leaves.remove(&(entry.used, code));
+      self.leaves.insert((self.clock, code));
+    }
+    entry.used = self.clock;
+  }
+
+  /// Makes room for a string extending `parent`, returning the code to `fill`.<br>
+  /// When the dictionary is full, the policy decides whether there's a code.
+  pub fn reserve(&mut self, parent: u32) -> Option<u32> {
+    if self.entries.len() < self.capacity {
+      self.entries.push(Entry {
+        parent: None,
+        byte: 0,
+        children: 0,
+        used: 0,
+      });
+      return Some(self.entries.len() as u32 - 1);
+    }
+
+    match self.policy {
+      LzwPolicy::Freeze => None,
+      LzwPolicy::Reset => {
+        self.entries.truncate(FIRST as usize);
+        self.entries.iter_mut().for_each(|entry| entry.children = 0);
+        self.lookup.clear();
+        self.leaves.clear();
+        None
+      }
+      LzwPolicy::Prune => {
+        // the parent can't go, or the new string would have nothing to extend
+        let &(used, code) = (self.leaves.iter()).find(|&&(_, code)| code != parent)?;
+        self.leaves.remove(&(used, code));
+        let entry = self.entries[code as usize];
+        if let Some(old) = entry.parent {
+          self.lookup.remove(&(old, entry.byte));
+          let old_entry = &mut self.entries[old as usize];
+          old_entry.children -= 1;
+          if old >= FIRST && old_entry.children == 0 {
+            self.leaves.insert((old_entry.used, old));
+          }
+        }
+        Some(code)
+      }
+    }
+  }
+
+  /// Sets the string for a reserved `code` to `parent`'s followed by `byte`
+  pub fn fill(&mut self, code: u32, parent: u32, byte: u8) {
+    let parent_entry = &mut self.entries[parent as usize];
+    if parent >= FIRST && parent_entry.children == 0 {
+      self.leaves.remove(&(parent_entry.used, parent));
+    }
+    parent_entry.children += 1;
+
+    self.clock += 1;
+    self.entries[code as usize] = Entry {
+      parent: Some(parent),
+      byte,
+      children: 0,
+      used: self.clock,
+    };
+    self.lookup.insert((parent, byte), code);
+    self.leaves.insert((self.clock, code));
+  }
+}
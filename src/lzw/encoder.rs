@@ -0,0 +1,75 @@
+use super::{Dictionary, LzwCompressor, LzwError, END};
+use crate::{IntoBits, Transform};
+
+/// LZW Encoding algorithm
+pub struct LzwEncoder {
+  dictionary: Dictionary,
+  bits: IntoBits<u32>,
+  /// The code for the longest string matched so far
+  current: Option<u32>,
+  ended: bool,
+}
+
+impl From<LzwCompressor> for LzwEncoder {
+  fn from(value: LzwCompressor) -> Self {
+    Self {
+      dictionary: value.dictionary(),
+      bits: IntoBits::default(),
+      current: None,
+      ended: false,
+    }
+  }
+}
+
+impl LzwEncoder {
+  /// Queues the code for the current string, and makes room for it to be<br>
+  /// extended. The decoder only learns how when it reads the next code.
+  fn emit(&mut self, code: u32) -> Option<u32> {
+    self.bits.push(code, self.dictionary.width());
+    self.dictionary.touch(code);
+    self.dictionary.reserve(code)
+  }
+
+  /// Reads bytes until there's a code to queue
+  fn encode(&mut self, iter: &mut impl Iterator<Item = u8>) {
+    for byte in iter.by_ref() {
+      let Some(code) = self.current else {
+        self.current = Some(byte as u32);
+        continue;
+      };
+      if let Some(child) = self.dictionary.child(code, byte) {
+        self.current = Some(child);
+        continue;
+      }
+
+      if let Some(slot) = self.emit(code) {
+        self.dictionary.fill(slot, code, byte);
+      }
+      self.current = Some(byte as u32);
+      return;
+    }
+
+    match self.current.take() {
+      Some(code) => _ = self.emit(code),
+      None => {
+        self.bits.push(END, self.dictionary.width());
+        self.ended = true;
+      }
+    }
+  }
+}
+
+impl Transform<u8> for LzwEncoder {
+  type Out = Result<bool, LzwError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    loop {
+      if let Some(bit) = self.bits.next_bit() {
+        return Some(Ok(bit));
+      }
+      if self.ended {
+        return None;
+      }
+      self.encode(iter);
+    }
+  }
+}
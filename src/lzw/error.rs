@@ -0,0 +1,21 @@
+use std::{error::Error, fmt};
+
+/// Errors produced when decoding LZW codes fails
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LzwError {
+  /// A code was read that isn't in the dictionary
+  InvalidCode,
+  /// The bits ran out before the end code
+  Truncated,
+}
+
+impl fmt::Display for LzwError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidCode => write!(f, "code is not in the dictionary"),
+      Self::Truncated => write!(f, "stream ended before the end code"),
+    }
+  }
+}
+
+impl Error for LzwError {}
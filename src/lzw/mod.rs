@@ -0,0 +1,77 @@
+use crate::Compressor;
+
+mod dictionary;
+use dictionary::{Dictionary, END};
+mod error;
+pub use error::LzwError;
+mod encoder;
+pub use encoder::LzwEncoder;
+mod decoder;
+pub use decoder::LzwDecoder;
+
+/// What happens once the dictionary has no free codes left
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum LzwPolicy {
+  /// Keep using the dictionary as it is, adding nothing more
+  Freeze,
+  /// Start again from a dictionary of single bytes
+  #[default]
+  Reset,
+  /// Replace the least recently used string that no other extends
+  Prune,
+}
+
+/// The LZW dictionary algorithm.<br>
+/// The longest string already in the dictionary is replaced with its code,<br>
+/// and that string followed by the next byte is added to the dictionary,<br>
+/// see Welch's [paper](https://doi.org/10.1109/MC.1984.1659158) for details.
+///
+/// Codes start at 9 bits and grow a bit at a time with the dictionary,<br>
+/// up to `max_bits`. The stream finishes with an end code.
+#[derive(Clone, Copy)]
+pub struct LzwCompressor {
+  max_bits: u32,
+  policy: LzwPolicy,
+}
+
+impl Default for LzwCompressor {
+  fn default() -> Self {
+    Self {
+      max_bits: 12,
+      policy: LzwPolicy::default(),
+    }
+  }
+}
+
+impl LzwCompressor {
+  /// Sets the widest code, limiting the dictionary to `1 << max_bits` codes.<br>
+  /// `12` by default, and must be between `9` and `24`.
+  pub fn with_max_bits(self, max_bits: u32) -> Self {
+    assert!(
+      (9..=24).contains(&max_bits),
+      "max_bits must be between 9 and 24"
+    );
+    Self { max_bits, ..self }
+  }
+
+  /// Sets what happens when the dictionary fills up, `Reset` by default
+  pub fn with_policy(self, policy: LzwPolicy) -> Self {
+    Self { policy, ..self }
+  }
+
+  fn dictionary(&self) -> Dictionary {
+    Dictionary::new(self.max_bits, self.policy)
+  }
+}
+
+unsafe impl Compressor for LzwCompressor {
+  type Error = LzwError;
+  type Item = u8;
+  type Data = bool;
+
+  type Encoder = LzwEncoder;
+  type Decoder = LzwDecoder;
+}
+
+#[cfg(test)]
+mod test;
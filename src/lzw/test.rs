@@ -0,0 +1,96 @@
+use crate::{test::CompressorTests, Compressor, IteratorTransforms};
+use proptest::{collection::vec, prop_oneof, proptest, strategy::Just};
+
+use super::{LzwCompressor, LzwError, LzwPolicy};
+
+proptest! {
+  #[test]
+  fn test_encode_decode(items: Vec<u8>) {
+    LzwCompressor::default().test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_repetitive_encode_decode(items: Vec<u8>, repeats in 1..16usize) {
+    LzwCompressor::default()
+      .test_encode_decode(items.repeat(repeats))
+      .unwrap()
+  }
+
+  #[test]
+  fn test_full_dictionary_encode_decode(
+    items in vec(0..8u8, 0..4096),
+    policy in prop_oneof![Just(LzwPolicy::Freeze), Just(LzwPolicy::Reset), Just(LzwPolicy::Prune)],
+  ) {
+    // 9 bit codes leave room for a few hundred strings, so the policy is used often
+    LzwCompressor::default()
+      .with_max_bits(9)
+      .with_policy(policy)
+      .test_encode_decode(items)
+      .unwrap()
+  }
+}
+
+#[test]
+fn test_compresses_repeats() {
+  let text = "the quick brown fox jumps over the lazy dog. ".repeat(64);
+  let encoded = (text.bytes())
+    .apply(LzwCompressor::default().encoder())
+    .count();
+  assert!(encoded < text.len() * 2, "{encoded} bits");
+}
+
+#[test]
+fn test_policies_adapt_to_changes() {
+  // the dictionary fills up on the first half, which says little about the second
+  let items: Vec<u8> = (0..1 << 14u32)
+    .map(|i| (i * i % 7) as u8)
+    .chain((0..1 << 14u32).map(|i| 100 + (i % 23) as u8 * (i / 23 % 3) as u8))
+    .collect();
+  let encoded_len = |policy| {
+    (items.iter().copied())
+      .apply(
+        LzwCompressor::default()
+          .with_max_bits(9)
+          .with_policy(policy)
+          .encoder(),
+      )
+      .count()
+  };
+
+  let frozen = encoded_len(LzwPolicy::Freeze);
+  let reset = encoded_len(LzwPolicy::Reset);
+  let pruned = encoded_len(LzwPolicy::Prune);
+  assert!(reset < frozen, "{reset} bits vs {frozen} bits");
+  assert!(pruned < frozen, "{pruned} bits vs {frozen} bits");
+}
+
+#[test]
+fn test_invalid_codes() {
+  let decode = |codes: &[u32]| {
+    (codes.iter())
+      .flat_map(|code| (0..9).rev().map(move |i| code >> i & 1 == 1))
+      .apply(LzwCompressor::default().decoder())
+      .collect::<Result<Vec<_>, _>>()
+  };
+
+  assert_eq!(decode(&[97, 98, 257, 256]), Ok(b"abab".to_vec()));
+  assert_eq!(decode(&[97, 257, 256]), Ok(b"aaa".to_vec()));
+  assert_eq!(decode(&[97, 259]), Err(LzwError::InvalidCode));
+  assert_eq!(decode(&[257]), Err(LzwError::InvalidCode));
+  assert_eq!(decode(&[97, 98]), Err(LzwError::Truncated));
+}
+
+proptest! {
+  #[test]
+  fn test_decode_arbitrary(bits: Vec<bool>) {
+    LzwCompressor::default().test_decode_arbitrary(bits)
+  }
+
+  #[test]
+  fn test_prune_decode_arbitrary(bits: Vec<bool>) {
+    LzwCompressor::default()
+      .with_max_bits(9)
+      .with_policy(LzwPolicy::Prune)
+      .test_decode_arbitrary(bits)
+  }
+}
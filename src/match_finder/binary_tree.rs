@@ -0,0 +1,123 @@
+use super::{hash, Match, MatchFinder, MatchWindow, HASH_BITS, HASH_BYTES};
+
+/// Finds matches with a binary search tree of earlier positions for each hash,<br>
+/// ordered by the bytes that follow them, as in LZMA's `bt` match finders.
+///
+/// Each search descends from the most recent position, rebuilding the tree<br>
+/// with the current position at its root, so long matches are found quickly.
+#[derive(Clone)]
+pub struct BinaryTree {
+  /// The root position of the tree for each hash
+  head: Vec<Option<usize>>,
+  /// The earlier and later sorted children of each position, indexed cyclically
+  children: Vec<[Option<usize>; 2]>,
+  /// The most tree nodes visited for each search
+  effort: usize,
+  /// The number of bytes hashed, which no shorter match shares
+  hash_bytes: usize,
+}
+
+/// Which child of a node comes first in order
+const SMALLER: usize = 0;
+const LARGER: usize = 1;
+
+impl BinaryTree {
+  /// A binary tree finder over a window of `size` bytes
+  pub fn new(size: usize) -> Self {
+    Self {
+      head: vec![None; 1 << HASH_BITS],
+      children: vec![[None; 2]; size + 1],
+      effort: 48,
+      hash_bytes: HASH_BYTES,
+    }
+  }
+
+  /// Sets the most tree nodes visited per search, `48` by default.<br>
+  /// Higher efforts find longer matches, but take longer to do so.
+  pub fn with_effort(self, effort: usize) -> Self {
+    assert!(effort > 0, "effort must be at least one");
+    Self { effort, ..self }
+  }
+
+  /// Finds matches as short as `min_match` bytes, `3` by default.<br>
+  /// Candidates are hashed on fewer bytes, so there are more of them.
+  pub fn with_min_match(self, min_match: usize) -> Self {
+    assert!(min_match > 0, "matches must be at least one byte");
+    Self {
+      hash_bytes: min_match.min(HASH_BYTES),
+      ..self
+    }
+  }
+
+  /// The children slot of `position`
+  fn slot(&self, position: usize) -> usize {
+    position % self.children.len()
+  }
+}
+
+impl MatchFinder for BinaryTree {
+  fn find(&mut self, window: &MatchWindow, max_length: usize) -> Option<Match> {
+    let (position, current) = (window.position(), window.lookahead());
+    if current.len() < self.hash_bytes {
+      return None;
+    }
+    let max_length = max_length.min(current.len());
+
+    let hash = hash(current, self.hash_bytes);
+    let mut candidate = self.head[hash].replace(position);
+
+    // where the next node smaller or larger than the current bytes is attached,
+    // along with how many bytes all nodes on that side are known to match
+    let root = self.slot(position);
+    let mut attach = [(root, SMALLER), (root, LARGER)];
+    let mut known = [0, 0];
+
+    let mut best: Option<Match> = None;
+    for _ in 0..self.effort {
+      let Some(start) = candidate else { break };
+      let distance = position - start;
+      if distance > window.size().min(self.children.len() - 1) {
+        break;
+      }
+
+      let earlier = window.bytes(start);
+      let skip = known[0].min(known[1]);
+      let length = skip
+        + (earlier[skip..].iter().zip(&current[skip..]))
+          .take(max_length - skip)
+          .take_while(|(a, b)| a == b)
+          .count();
+      if best.is_none_or(|best| length > best.length) {
+        best = Some(Match { distance, length });
+      }
+
+      let node = self.slot(start);
+      if length == max_length {
+        // the current position replaces the node, which is no longer needed
+        let [smaller, larger] = self.children[node];
+        let ((s, s_side), (l, l_side)) = (attach[0], attach[1]);
+        self.children[s][s_side] = smaller;
+        self.children[l][l_side] = larger;
+        return best;
+      }
+
+      // the node goes on the side it sorts on, taking its subtree on that
+      // side with it, then the search continues into its other subtree
+      let side = match earlier[length] < current[length] {
+        true => SMALLER,
+        false => LARGER,
+      };
+      let (parent, parent_side) = attach[side];
+      self.children[parent][parent_side] = Some(start);
+      attach[side] = (node, 1 - side);
+      known[side] = length;
+      candidate = self.children[node][1 - side];
+    }
+
+    // anything left is too far back or beyond the effort
+    let ((s, s_side), (l, l_side)) = (attach[0], attach[1]);
+    self.children[s][s_side] = None;
+    self.children[l][l_side] = None;
+    best.filter(|best| best.length > 0)
+  }
+}
@@ -0,0 +1,81 @@
+use super::{hash, Match, MatchFinder, MatchWindow, HASH_BITS, HASH_BYTES};
+
+/// Finds matches by following chains of earlier positions with the same hash,<br>
+/// as in zlib. Cheap to update, but slow when there are many candidates.
+#[derive(Clone)]
+pub struct HashChain {
+  /// The most recent position for each hash
+  head: Vec<Option<usize>>,
+  /// The previous position with the same hash, indexed cyclically
+  prev: Vec<Option<usize>>,
+  /// The most candidates compared for each search
+  effort: usize,
+  /// The number of bytes hashed, which no shorter match shares
+  hash_bytes: usize,
+}
+
+impl HashChain {
+  /// A hash chain over a window of `size` bytes
+  pub fn new(size: usize) -> Self {
+    Self {
+      head: vec![None; 1 << HASH_BITS],
+      prev: vec![None; size + 1],
+      effort: 128,
+      hash_bytes: HASH_BYTES,
+    }
+  }
+
+  /// Sets the most candidates compared per search, `128` by default.<br>
+  /// Higher efforts find longer matches, but take longer to do so.
+  pub fn with_effort(self, effort: usize) -> Self {
+    assert!(effort > 0, "effort must be at least one");
+    Self { effort, ..self }
+  }
+
+  /// Finds matches as short as `min_match` bytes, `3` by default.<br>
+  /// Candidates are hashed on fewer bytes, so there are more of them.
+  pub fn with_min_match(self, min_match: usize) -> Self {
+    assert!(min_match > 0, "matches must be at least one byte");
+    Self {
+      hash_bytes: min_match.min(HASH_BYTES),
+      ..self
+    }
+  }
+}
+
+impl MatchFinder for HashChain {
+  fn find(&mut self, window: &MatchWindow, max_length: usize) -> Option<Match> {
+    let (position, current) = (window.position(), window.lookahead());
+    if current.len() < self.hash_bytes {
+      return None;
+    }
+    let max_length = max_length.min(current.len());
+
+    let hash = hash(current, self.hash_bytes);
+    let mut candidate = self.head[hash].replace(position);
+    let slot = position % self.prev.len();
+    self.prev[slot] = candidate;
+
+    let mut best: Option<Match> = None;
+    for _ in 0..self.effort {
+      let Some(start) = candidate else { break };
+      let distance = position - start;
+      if distance > window.size().min(self.prev.len() - 1) {
+        break;
+      }
+
+      let length = (window.bytes(start).iter().zip(current))
+        .take(max_length)
+        .take_while(|(a, b)| a == b)
+        .count();
+      if best.is_none_or(|best| length > best.length) {
+        best = Some(Match { distance, length });
+      }
+      if length == max_length {
+        break;
+      }
+      candidate = self.prev[start % self.prev.len()];
+    }
+    best.filter(|best| best.length > 0)
+  }
+}
@@ -0,0 +1,100 @@
+mod window;
+pub use window::MatchWindow;
+mod hash_chain;
+pub use hash_chain::HashChain;
+mod binary_tree;
+pub use binary_tree::BinaryTree;
+
+/// The number of bytes hashed to find candidate matches
+const HASH_BYTES: usize = 3;
+/// The number of bits in those hashes
+const HASH_BITS: u32 = 16;
+
+/// Hashes the first `length` of `bytes`, at most `HASH_BYTES`
+fn hash(bytes: &[u8], length: usize) -> usize {
+  let value = (bytes[..length].iter()).fold(0u32, |hash, byte| (hash << 8) | u32::from(*byte));
+  (value.wrapping_mul(0x9E37_79B1) >> (u32::BITS - HASH_BITS)) as usize
+}
+
+/// A match found by a `MatchFinder`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Match {
+  /// How far back the match starts, from `1` to the window size
+  pub distance: usize,
+  /// The number of bytes that match
+  pub length: usize,
+}
+
+/// A strategy for finding the longest earlier match for the bytes at<br>
+/// a `MatchWindow`'s position, for dictionary compressors.
+///
+/// Positions must be visited in order, each with either `find` or `skip`,<br>
+/// so the finder sees every position before searching past it.
+pub trait MatchFinder {
+  /// The longest match of up to `max_length` bytes for the bytes at<br>
+  /// `window.position()`, then adds that position for later searches.
+  fn find(&mut self, window: &MatchWindow, max_length: usize) -> Option<Match>;
+
+  /// Adds the bytes at `window.position()` for later searches,<br>
+  /// for positions covered by a match that don't need searching.
+  fn skip(&mut self, window: &MatchWindow, max_length: usize) {
+    self.find(window, max_length);
+  }
+}
+
+impl<M: MatchFinder + ?Sized> MatchFinder for Box<M> {
+  fn find(&mut self, window: &MatchWindow, max_length: usize) -> Option<Match> {
+    (**self).find(window, max_length)
+  }
+
+  fn skip(&mut self, window: &MatchWindow, max_length: usize) {
+    (**self).skip(window, max_length)
+  }
+}
+
+/// Builds a `MatchFinder` once encoding starts, so compressors only<br>
+/// hold its settings rather than its tables.
+pub trait BuildMatchFinder {
+  /// The finder built
+  type Finder: MatchFinder;
+
+  /// A finder over a window of `size` bytes, for matches of at least<br>
+  /// `min_match` bytes, with the given effort or else its default
+  fn build(&self, size: usize, min_match: usize, effort: Option<usize>) -> Self::Finder;
+}
+
+/// The finders in this crate, for picking one at runtime
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MatchFinderKind {
+  /// A `HashChain`, cheap to update
+  #[default]
+  HashChain,
+  /// A `BinaryTree`, quicker to find long matches among many candidates
+  BinaryTree,
+}
+
+impl BuildMatchFinder for MatchFinderKind {
+  type Finder = Box<dyn MatchFinder>;
+
+  fn build(&self, size: usize, min_match: usize, effort: Option<usize>) -> Self::Finder {
+    match self {
+      Self::HashChain => {
+        let finder = HashChain::new(size).with_min_match(min_match);
+        match effort {
+          Some(effort) => Box::new(finder.with_effort(effort)),
+          None => Box::new(finder),
+        }
+      }
+      Self::BinaryTree => {
+        let finder = BinaryTree::new(size).with_min_match(min_match);
+        match effort {
+          Some(effort) => Box::new(finder.with_effort(effort)),
+          None => Box::new(finder),
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test;
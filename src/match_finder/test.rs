@@ -0,0 +1,120 @@
+use proptest::{collection::vec, prop_assert, prop_assert_eq, proptest};
+
+use super::{BinaryTree, HashChain, Match, MatchFinder, MatchWindow, HASH_BYTES};
+
+/// The longest match at each position, found by comparing against every earlier one
+fn brute_force(items: &[u8], size: usize, max_length: usize) -> Vec<usize> {
+  (0..items.len())
+    .map(|position| {
+      (position.saturating_sub(size)..position)
+        .map(|start| {
+          (items[start..].iter().zip(&items[position..]))
+            .take(max_length)
+            .take_while(|(a, b)| a == b)
+            .count()
+        })
+        .max()
+        .unwrap_or(0)
+    })
+    .collect()
+}
+
+/// The match `finder` finds at each position
+fn find_all(
+  mut finder: impl MatchFinder,
+  items: &[u8],
+  size: usize,
+  max_length: usize,
+) -> Vec<Option<Match>> {
+  let mut window = MatchWindow::new(size);
+  items.iter().for_each(|item| window.push(*item));
+  (0..items.len())
+    .map(|_| {
+      let found = finder.find(&window, max_length);
+      window.advance();
+      found
+    })
+    .collect()
+}
+
+/// Checks `found` against the longest matches, which all finders find
+/// given enough effort, as long as they're at least `hashed` bytes long
+fn check(
+  items: &[u8],
+  found: &[Option<Match>],
+  longest: &[usize],
+  size: usize,
+  hashed: usize,
+) -> Result<(), proptest::test_runner::TestCaseError> {
+  for (position, (found, longest)) in found.iter().zip(longest).enumerate() {
+    let length = found.map_or(0, |found| found.length);
+    if let Some(Match { distance, length }) = *found {
+      prop_assert!((1..=size.min(position)).contains(&distance));
+      let start = position - distance;
+      prop_assert_eq!(
+        &items[start..start + length],
+        &items[position..position + length]
+      );
+    }
+    match *longest >= hashed {
+      true => prop_assert_eq!(length, *longest),
+      false => prop_assert!(length <= *longest),
+    }
+  }
+  Ok(())
+}
+
+proptest! {
+  #[test]
+  fn test_hash_chain_finds_longest(items in vec(0..3u8, 0..512), size in 1..64usize, max_length in 3..32usize) {
+    let longest = brute_force(&items, size, max_length);
+    let found = find_all(HashChain::new(size).with_effort(usize::MAX), &items, size, max_length);
+    check(&items, &found, &longest, size, HASH_BYTES)?;
+  }
+
+  #[test]
+  fn test_binary_tree_finds_longest(items in vec(0..3u8, 0..512), size in 1..64usize, max_length in 3..32usize) {
+    let longest = brute_force(&items, size, max_length);
+    let found = find_all(BinaryTree::new(size).with_effort(usize::MAX), &items, size, max_length);
+    check(&items, &found, &longest, size, HASH_BYTES)?;
+  }
+
+  #[test]
+  fn test_short_matches_found(items in vec(0..3u8, 0..512), size in 1..64usize, min_match in 1..3usize) {
+    let longest = brute_force(&items, size, 16);
+    for found in [
+      find_all(HashChain::new(size).with_effort(usize::MAX).with_min_match(min_match), &items, size, 16),
+      find_all(BinaryTree::new(size).with_effort(usize::MAX).with_min_match(min_match), &items, size, 16),
+    ] {
+      check(&items, &found, &longest, size, min_match)?;
+    }
+  }
+
+  #[test]
+  fn test_low_effort_matches_are_valid(items in vec(0..3u8, 0..512), size in 1..64usize) {
+    let longest = brute_force(&items, size, 16);
+    for found in [
+      find_all(HashChain::new(size).with_effort(1), &items, size, 16),
+      find_all(BinaryTree::new(size).with_effort(1), &items, size, 16),
+    ] {
+      for (position, found) in found.into_iter().enumerate() {
+        let Some(Match { distance, length }) = found else { continue };
+        prop_assert!(length <= longest[position]);
+        let start = position - distance;
+        prop_assert_eq!(&items[start..start + length], &items[position..position + length]);
+      }
+    }
+  }
+}
+
+#[test]
+fn test_window_slides() {
+  let mut window = MatchWindow::new(4);
+  (0..64).for_each(|item| window.push(item));
+  for _ in 0..60 {
+    window.advance();
+  }
+  assert_eq!(window.position(), 60);
+  assert_eq!(window.lookahead(), &[60, 61, 62, 63]);
+  assert_eq!(window.bytes(56), &[56, 57, 58, 59, 60, 61, 62, 63]);
+}
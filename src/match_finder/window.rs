@@ -0,0 +1,60 @@
+/// A sliding window of bytes for match finders to search:<br>
+/// the bytes already seen, followed by those still to be matched.
+#[derive(Clone)]
+pub struct MatchWindow {
+  /// Bytes from position `base` onwards
+  buffer: Vec<u8>,
+  base: usize,
+  position: usize,
+  size: usize,
+}
+
+impl MatchWindow {
+  /// A window keeping the last `size` bytes before the position
+  pub fn new(size: usize) -> Self {
+    assert!(size > 0, "window must hold at least one byte");
+    Self {
+      buffer: Vec::new(),
+      base: 0,
+      position: 0,
+      size,
+    }
+  }
+
+  /// How far back matches can reach
+  pub fn size(&self) -> usize {
+    self.size
+  }
+
+  /// The number of bytes before the current one
+  pub fn position(&self) -> usize {
+    self.position
+  }
+
+  /// Appends a byte after the bytes still to be matched
+  pub fn push(&mut self, byte: u8) {
+    self.buffer.push(byte);
+  }
+
+  /// The bytes from the current one onwards
+  pub fn lookahead(&self) -> &[u8] {
+    &self.buffer[self.position - self.base..]
+  }
+
+  /// The bytes from `position` onwards, which must be<br>
+  /// within the window or ahead of it.
+  pub fn bytes(&self, position: usize) -> &[u8] {
+    &self.buffer[position - self.base..]
+  }
+
+  /// Moves past the current byte, forgetting bytes that have<br>
+  /// fallen out of the window once there's a window's worth of them.
+  pub fn advance(&mut self) {
+    self.position += 1;
+    let start = self.position.saturating_sub(self.size);
+    if start - self.base >= self.size {
+      self.buffer.drain(..start - self.base);
+      self.base = start;
+    }
+  }
+}
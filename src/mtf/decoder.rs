@@ -0,0 +1,38 @@
+use super::{Mtf, RankList};
+use crate::Transform;
+
+/// The inverse move-to-front transform
+#[derive(Clone)]
+pub struct MtfDecoder {
+  list: RankList,
+}
+
+impl From<Mtf> for MtfDecoder {
+  fn from(value: Mtf) -> Self {
+    Self {
+      list: value.variant.into(),
+    }
+  }
+}
+
+impl Transform<u8> for MtfDecoder {
+  type Out = u8;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    Some(self.list.decode(iter.next()?))
+  }
+
+  fn size_hint(&self, iter: &impl Iterator) -> (usize, Option<usize>) {
+    iter.size_hint()
+  }
+}
+
+impl<E> Transform<Result<u8, E>> for MtfDecoder {
+  type Out = Result<u8, E>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = Result<u8, E>>) -> Option<Self::Out> {
+    Some(iter.next()?.map(|rank| self.list.decode(rank)))
+  }
+
+  fn size_hint(&self, iter: &impl Iterator) -> (usize, Option<usize>) {
+    iter.size_hint()
+  }
+}
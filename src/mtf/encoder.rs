@@ -0,0 +1,38 @@
+use super::{Mtf, RankList};
+use crate::Transform;
+
+/// The forward move-to-front transform
+#[derive(Clone)]
+pub struct MtfEncoder {
+  list: RankList,
+}
+
+impl From<Mtf> for MtfEncoder {
+  fn from(value: Mtf) -> Self {
+    Self {
+      list: value.variant.into(),
+    }
+  }
+}
+
+impl Transform<u8> for MtfEncoder {
+  type Out = u8;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    Some(self.list.encode(iter.next()?))
+  }
+
+  fn size_hint(&self, iter: &impl Iterator) -> (usize, Option<usize>) {
+    iter.size_hint()
+  }
+}
+
+impl<E> Transform<Result<u8, E>> for MtfEncoder {
+  type Out = Result<u8, E>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = Result<u8, E>>) -> Option<Self::Out> {
+    Some(iter.next()?.map(|byte| self.list.encode(byte)))
+  }
+
+  fn size_hint(&self, iter: &impl Iterator) -> (usize, Option<usize>) {
+    iter.size_hint()
+  }
+}
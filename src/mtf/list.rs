@@ -0,0 +1,77 @@
+use super::MtfVariant;
+
+/// The count at which every `WeightedFrequency` count is halved
+const WEIGHT_LIMIT: u32 = 1 << 12;
+
+/// A list of every byte, ordered by a recency ranking
+#[derive(Clone)]
+pub(crate) struct RankList {
+  order: [u8; 256],
+  variant: MtfVariant,
+  /// The number of bytes seen so far
+  clock: u64,
+  /// When each byte was last seen, `0` if it hasn't been
+  last: [u64; 256],
+  /// When each byte was seen before that
+  before_last: [u64; 256],
+  weights: [u32; 256],
+}
+
+impl From<MtfVariant> for RankList {
+  fn from(variant: MtfVariant) -> Self {
+    Self {
+      order: std::array::from_fn(|i| i as u8),
+      variant,
+      clock: 0,
+      last: [0; 256],
+      before_last: [0; 256],
+      weights: [0; 256],
+    }
+  }
+}
+
+impl RankList {
+  /// The rank of `byte`, moving it up the list
+  pub fn encode(&mut self, byte: u8) -> u8 {
+    let Some(rank) = self.order.iter().position(|&b| b == byte) else {
+      unreachable!("the list holds every byte")
+    };
+    self.update(rank);
+    rank as u8
+  }
+
+  /// The byte at `rank`, moving it up the list
+  pub fn decode(&mut self, rank: u8) -> u8 {
+    let byte = self.order[rank as usize];
+    self.update(rank as usize);
+    byte
+  }
+
+  /// Moves the byte at `rank` up the list, after it has been seen
+  fn update(&mut self, rank: usize) {
+    let byte = self.order[rank] as usize;
+    self.clock += 1;
+    let target = match self.variant {
+      MtfVariant::MoveToFront => 0,
+      MtfVariant::MoveToSecond => usize::from(rank > 1),
+      // a byte seen for the first time stays where it is
+      MtfVariant::Timestamp if self.last[byte] == 0 => rank,
+      MtfVariant::Timestamp => (self.order[..rank].iter())
+        .position(|&other| self.before_last[other as usize] < self.last[byte])
+        .unwrap_or(rank),
+      MtfVariant::WeightedFrequency => {
+        self.weights[byte] += 1;
+        if self.weights[byte] > WEIGHT_LIMIT {
+          // halving keeps the list in order, though some counts may become equal
+          self.weights.iter_mut().for_each(|weight| *weight /= 2);
+        }
+        (self.order[..rank].iter())
+          .position(|&other| self.weights[other as usize] <= self.weights[byte])
+          .unwrap_or(rank)
+      }
+    };
+    self.before_last[byte] = self.last[byte];
+    self.last[byte] = self.clock;
+    self.order[target..=rank].rotate_right(1);
+  }
+}
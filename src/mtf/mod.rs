@@ -0,0 +1,54 @@
+mod list;
+use list::RankList;
+mod encoder;
+pub use encoder::MtfEncoder;
+mod decoder;
+pub use decoder::MtfDecoder;
+
+/// How a byte moves up the list once its rank has been output
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MtfVariant {
+  /// Straight to the front
+  #[default]
+  MoveToFront,
+  /// To the front from second place, and to second place from anywhere else,<br>
+  /// so a single stray byte doesn't push the most recent one back (MTF-1)
+  MoveToSecond,
+  /// In front of the first byte seen at most once since this byte was last seen,<br>
+  /// see Albers' [paper](https://doi.org/10.1007/BFb0055082) on TS(0) for details
+  Timestamp,
+  /// In front of every byte seen no more often, counting recent bytes more<br>
+  /// by halving every count once one gets large
+  WeightedFrequency,
+}
+
+/// The move-to-front transform, and related recency ranking transforms.<br>
+/// Each byte is replaced by its rank in a list of every byte, and then moves<br>
+/// up the list, so recently seen bytes get small ranks.
+///
+/// Runs of similar bytes, like those left by the Burrows-Wheeler transform,<br>
+/// become runs of zeros and small ranks that entropy code well.
+#[derive(Clone, Copy, Default)]
+pub struct Mtf {
+  variant: MtfVariant,
+}
+
+impl Mtf {
+  /// Sets how bytes move up the list, `MoveToFront` by default
+  pub fn with_variant(self, variant: MtfVariant) -> Self {
+    Self { variant }
+  }
+
+  /// The forward transform
+  pub fn encoder(self) -> MtfEncoder {
+    self.into()
+  }
+
+  /// The inverse transform
+  pub fn decoder(self) -> MtfDecoder {
+    self.into()
+  }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,85 @@
+use crate::{Bwt, IteratorTransforms, Transform};
+use proptest::{prop_oneof, proptest, strategy::Just};
+
+use super::{Mtf, MtfVariant};
+
+/// The ranks output for `items`
+fn ranks(variant: MtfVariant, items: &[u8]) -> Vec<u8> {
+  (items.iter().copied())
+    .apply(Mtf::default().with_variant(variant).encoder())
+    .collect()
+}
+
+proptest! {
+  #[test]
+  fn test_encode_decode(
+    items: Vec<u8>,
+    variant in prop_oneof![
+      Just(MtfVariant::MoveToFront),
+      Just(MtfVariant::MoveToSecond),
+      Just(MtfVariant::Timestamp),
+      Just(MtfVariant::WeightedFrequency),
+    ],
+  ) {
+    let mtf = Mtf::default().with_variant(variant);
+    let decoded: Vec<_> = (items.iter().copied())
+      .apply(mtf.encoder())
+      .apply(mtf.decoder())
+      .collect();
+    assert_eq!(decoded, items);
+  }
+}
+
+#[test]
+fn test_move_to_front() {
+  assert_eq!(
+    ranks(MtfVariant::MoveToFront, b"bananaaa"),
+    [98, 98, 110, 1, 1, 1, 0, 0]
+  );
+}
+
+#[test]
+fn test_move_to_second() {
+  // new bytes only reach second place, so `a` keeps a rank of zero after `b`
+  assert_eq!(
+    ranks(MtfVariant::MoveToSecond, b"aabaab"),
+    [97, 1, 98, 0, 0, 1]
+  );
+}
+
+#[test]
+fn test_timestamp() {
+  // bytes only move once they've been seen twice, and the last `b`
+  // can't pass `a`, which has been seen twice since
+  assert_eq!(ranks(MtfVariant::Timestamp, b"bbaab"), [98, 98, 98, 98, 1]);
+}
+
+#[test]
+fn test_weighted_frequency() {
+  // `a` is seen more often, so a single `b` doesn't overtake it
+  assert_eq!(
+    ranks(MtfVariant::WeightedFrequency, b"aaaba"),
+    [97, 0, 0, 98, 0]
+  );
+}
+
+#[test]
+fn test_after_bwt() {
+  let text = "she sells sea shells by the sea shore. ".repeat(64);
+  let bwt = Bwt::default();
+  let ranks: Vec<_> = (text.bytes())
+    .apply(bwt.encoder().then(Mtf::default().encoder()))
+    .collect();
+  let zeros = ranks.iter().filter(|&&rank| rank == 0).count();
+  assert!(
+    zeros > ranks.len() * 3 / 4,
+    "{zeros} zeros of {}",
+    ranks.len()
+  );
+
+  let decoded = (ranks.into_iter())
+    .apply(Mtf::default().decoder())
+    .apply(bwt.decoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(decoded, Ok(text.into_bytes()));
+}
@@ -0,0 +1,66 @@
+use super::{
+  model::{PpmModel, Symbol},
+  PpmCompressor,
+};
+use crate::{arithmetic::IntervalDecoder, utils::end_after, ArithmeticError, IntoBits, Transform};
+
+/// PPM Decoding algorithm
+pub struct PpmDecoder {
+  model: PpmModel,
+  interval: IntervalDecoder,
+  finished: bool,
+}
+
+impl From<PpmCompressor> for PpmDecoder {
+  fn from(value: PpmCompressor) -> Self {
+    Self {
+      model: PpmModel::new(value.order),
+      interval: IntervalDecoder::default(),
+      finished: false,
+    }
+  }
+}
+
+impl From<PpmCompressor> for (IntoBits<u8>, PpmDecoder) {
+  fn from(value: PpmCompressor) -> Self {
+    (Default::default(), value.into())
+  }
+}
+
+impl PpmDecoder {
+  /// Decodes escapes until reaching a context that codes a byte,<br>
+  /// returning the byte or `None` at the end marker.
+  fn decode(
+    &mut self,
+    iter: &mut impl Iterator<Item = bool>,
+  ) -> Result<Option<u8>, ArithmeticError> {
+    loop {
+      let (ranges, len) = self.model.ranges();
+      let i = self.interval.target(iter, len)?;
+
+      let &(symbol, range) = (ranges.iter())
+        .find(|(_, (l, h))| (*l <= i) & (i < *h))
+        .ok_or(ArithmeticError::OutOfRange)?;
+      self.interval.decode(range, len);
+      self.model.update(symbol, &ranges);
+
+      match symbol {
+        Symbol::Byte(byte) => return Ok(Some(byte)),
+        Symbol::End => return Ok(None),
+        Symbol::Escape => {}
+      }
+    }
+  }
+}
+
+impl Transform<bool> for PpmDecoder {
+  type Out = Result<u8, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = bool>) -> Option<Self::Out> {
+    if self.finished {
+      return None;
+    }
+
+    let decoded = self.decode(iter);
+    end_after(&mut self.finished, decoded)
+  }
+}
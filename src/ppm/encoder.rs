@@ -0,0 +1,93 @@
+use super::{
+  model::{PpmModel, Symbol},
+  PpmCompressor,
+};
+use crate::{arithmetic::IntervalEncoder, ArithmeticError, Code, FromBits, Transform};
+use std::collections::VecDeque;
+
+/// PPM Encoding algorithm
+pub struct PpmEncoder {
+  model: PpmModel,
+  interval: IntervalEncoder,
+  /// Frequency ranges still to be encoded for the current byte
+  queue: VecDeque<((Code, Code), Code)>,
+  finished: bool,
+  failed: bool,
+}
+
+impl From<PpmCompressor> for PpmEncoder {
+  fn from(value: PpmCompressor) -> Self {
+    Self {
+      model: PpmModel::new(value.order),
+      interval: IntervalEncoder::default(),
+      queue: VecDeque::new(),
+      finished: false,
+      failed: false,
+    }
+  }
+}
+
+impl From<PpmCompressor> for (PpmEncoder, FromBits<u8>) {
+  fn from(value: PpmCompressor) -> Self {
+    (value.into(), Default::default())
+  }
+}
+
+impl PpmEncoder {
+  /// Queues up the escapes needed to reach a context that predicts `target`,<br>
+  /// followed by `target` itself.
+  fn push(&mut self, target: Symbol) {
+    loop {
+      let (ranges, len) = self.model.ranges();
+      let Some(&(symbol, range)) = (ranges.iter())
+        .find(|(symbol, _)| *symbol == target)
+        .or_else(|| ranges.iter().find(|(symbol, _)| *symbol == Symbol::Escape))
+      else {
+        unreachable!("order -1 can code every symbol not yet excluded")
+      };
+
+      self.queue.push_back((range, len));
+      self.model.update(symbol, &ranges);
+      if symbol != Symbol::Escape {
+        break;
+      }
+    }
+  }
+}
+
+impl Transform<u8> for PpmEncoder {
+  type Out = Result<bool, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    if self.failed {
+      return None;
+    }
+
+    loop {
+      // output bits until the range is wide enough for the next symbol
+      if let Some(bit) = self.interval.poll() {
+        return Some(Ok(bit));
+      }
+
+      if let Some((range, len)) = self.queue.pop_front() {
+        if let Err(e) = self.interval.encode(range, len) {
+          self.failed = true;
+          return Some(Err(e));
+        }
+        continue;
+      }
+
+      if self.finished {
+        return self.interval.flush().map(Ok);
+      }
+
+      // queue the next byte or mark the end of the stream
+      match iter.next() {
+        Some(byte) => self.push(Symbol::Byte(byte)),
+        None => {
+          self.push(Symbol::End);
+          self.finished = true;
+        }
+      }
+    }
+  }
+}
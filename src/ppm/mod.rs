@@ -0,0 +1,41 @@
+use crate::{ArithmeticError, Compressor};
+
+mod encoder;
+mod model;
+pub use encoder::PpmEncoder;
+mod decoder;
+pub use decoder::PpmDecoder;
+
+/// Prediction by partial matching, on top of arithmetic coding.<br>
+/// Each byte is predicted from the previous `order` bytes, escaping<br>
+/// to shorter contexts whenever the longer ones haven't seen the byte.<br>
+/// See Cleary & Witten's [paper](https://doi.org/10.1109/TCOM.1984.1096090) for details.
+#[derive(Clone, Copy)]
+pub struct PpmCompressor {
+  order: usize,
+}
+
+impl Default for PpmCompressor {
+  fn default() -> Self {
+    Self::new(3)
+  }
+}
+
+impl PpmCompressor {
+  /// A PPM compressor using contexts of up to `order` previous bytes
+  pub fn new(order: usize) -> Self {
+    Self { order }
+  }
+}
+
+unsafe impl Compressor for PpmCompressor {
+  type Error = ArithmeticError;
+  type Item = u8;
+  type Data = bool;
+
+  type Encoder = PpmEncoder;
+  type Decoder = PpmDecoder;
+}
+
+#[cfg(test)]
+mod test;
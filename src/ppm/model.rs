@@ -0,0 +1,155 @@
+use crate::{Code, FREQ_MAX};
+use std::collections::HashMap;
+
+/// The symbols coded by a PPM model,<br>
+/// escapes fall back to a shorter context.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Symbol {
+  Byte(u8),
+  Escape,
+  End,
+}
+
+/// The frequency range of each symbol codable in a context
+pub(crate) type Ranges = Vec<(Symbol, (Code, Code))>;
+
+/// Symbol counts seen in a single context, in the order first seen
+#[derive(Clone, Default)]
+struct Context(Vec<(u8, Code)>);
+
+impl Context {
+  /// Adds one to the count of `byte`, halving counts if the context fills up
+  fn increment(&mut self, byte: u8) {
+    match self.0.iter_mut().find(|(b, _)| *b == byte) {
+      Some((_, count)) => *count += 1,
+      None => self.0.push((byte, 1)),
+    }
+
+    // leave room for the escape symbol, which is as frequent
+    // as the number of distinct symbols seen in this context
+    let total: Code = self.0.iter().map(|(_, count)| count).sum();
+    if total + self.0.len() as Code > FREQ_MAX / 2 {
+      for (_, count) in &mut self.0 {
+        *count = count.div_ceil(2);
+      }
+    }
+  }
+
+  /// The frequency ranges of every symbol not in `excluded`, followed by<br>
+  /// the escape symbol (PPM method C), alongside the total frequency.
+  fn ranges(&self, excluded: &[bool; 256]) -> (Ranges, Code) {
+    let mut ranges = Vec::with_capacity(self.0.len() + 1);
+    let mut low = 0;
+    for &(byte, count) in &self.0 {
+      if !excluded[byte as usize] {
+        ranges.push((Symbol::Byte(byte), (low, low + count)));
+        low += count;
+      }
+    }
+
+    // contexts where every symbol's been excluded can't code anything
+    if ranges.is_empty() {
+      return (ranges, 0);
+    }
+    let escape = ranges.len() as Code;
+    ranges.push((Symbol::Escape, (low, low + escape)));
+    (ranges, low + escape)
+  }
+}
+
+/// A prediction by partial matching model over bytes.
+///
+/// Bytes are predicted by the longest context of previous bytes<br>
+/// that they've been seen in, escaping to shorter contexts and<br>
+/// eventually to a uniform distribution over bytes and `End`.
+#[derive(Clone)]
+pub(crate) struct PpmModel {
+  order: usize,
+  history: Vec<u8>,
+  contexts: HashMap<Vec<u8>, Context>,
+  excluded: [bool; 256],
+  /// The length of the context that will be used next, `None` for order -1
+  current: Option<usize>,
+}
+
+impl PpmModel {
+  pub fn new(order: usize) -> Self {
+    let mut model = Self {
+      order,
+      history: Vec::with_capacity(order),
+      contexts: HashMap::new(),
+      excluded: [false; 256],
+      current: None,
+    };
+    model.reset();
+    model
+  }
+
+  /// Starts predicting a new byte from the longest context available
+  fn reset(&mut self) {
+    self.excluded = [false; 256];
+    self.current = Some(self.history.len());
+  }
+
+  /// The frequency ranges of the symbols codable in the current context.<br>
+  /// Contexts that can't code any symbols are skipped over.
+  pub fn ranges(&mut self) -> (Ranges, Code) {
+    while let Some(order) = self.current {
+      let context = &self.history[self.history.len() - order..];
+      if let Some(context) = self.contexts.get(context) {
+        let (ranges, len) = context.ranges(&self.excluded);
+        if len > 0 {
+          return (ranges, len);
+        }
+      }
+      self.current = order.checked_sub(1);
+    }
+
+    // order -1: every byte that's not been excluded, plus `End`
+    let ranges: Vec<_> = (0..=255)
+      .filter(|&byte| !self.excluded[byte as usize])
+      .map(Symbol::Byte)
+      .chain([Symbol::End])
+      .enumerate()
+      .map(|(i, symbol)| (symbol, (i as Code, i as Code + 1)))
+      .collect();
+    let len = ranges.len() as Code;
+    (ranges, len)
+  }
+
+  /// Updates the model after `symbol` has been coded from `ranges`
+  pub fn update(&mut self, symbol: Symbol, ranges: &[(Symbol, (Code, Code))]) {
+    match symbol {
+      Symbol::Escape => {
+        for (symbol, _) in ranges {
+          if let Symbol::Byte(byte) = symbol {
+            self.excluded[*byte as usize] = true;
+          }
+        }
+        self.current = self.current.and_then(|order| order.checked_sub(1));
+      }
+      Symbol::Byte(byte) => {
+        for order in 0..=self.history.len() {
+          let context = &self.history[self.history.len() - order..];
+          match self.contexts.get_mut(context) {
+            Some(context) => context.increment(byte),
+            None => {
+              let mut new = Context::default();
+              new.increment(byte);
+              self.contexts.insert(context.to_vec(), new);
+            }
+          }
+        }
+
+        if self.order > 0 {
+          if self.history.len() == self.order {
+            self.history.remove(0);
+          }
+          self.history.push(byte);
+        }
+        self.reset();
+      }
+      Symbol::End => self.reset(),
+    }
+  }
+}
@@ -0,0 +1,51 @@
+use crate::{
+  test::CompressorTests, ArithmeticCompressor, ArithmeticError, Compressor, IteratorTransforms,
+};
+use proptest::proptest;
+
+use super::PpmCompressor;
+
+proptest! {
+  #[test]
+  fn test_encode_decode(items: Vec<u8>) {
+    PpmCompressor::default().test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_order_0_encode_decode(items: Vec<u8>) {
+    PpmCompressor::new(0).test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_repetitive_encode_decode(items: Vec<u8>, repeats in 1..16usize) {
+    PpmCompressor::new(4).test_encode_decode(items.repeat(repeats)).unwrap()
+  }
+}
+
+#[test]
+fn test_beats_order_0_on_text() {
+  let text = "the quick brown fox jumps over the lazy dog. ".repeat(64);
+  let encoded_len = |items: &[u8], compressor: PpmCompressor| {
+    items.iter().copied().apply(compressor.encoder()).count()
+  };
+
+  let order_0 = (text.bytes())
+    .apply(ArithmeticCompressor::adaptive().encoder())
+    .count();
+  let order_3 = encoded_len(text.as_bytes(), PpmCompressor::new(3));
+  assert!(order_3 < order_0 / 4, "{order_3} bits vs {order_0} bits");
+}
+
+#[test]
+fn test_truncated() {
+  let compressor = PpmCompressor::default();
+  compressor.test_truncated(Vec::new(), 0, ArithmeticError::Truncated);
+  compressor.test_truncated((0..=255).collect(), 512, ArithmeticError::Truncated);
+}
+
+proptest! {
+  #[test]
+  fn test_decode_arbitrary(bits: Vec<bool>) {
+    PpmCompressor::default().test_decode_arbitrary(bits)
+  }
+}
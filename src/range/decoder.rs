@@ -0,0 +1,92 @@
+use super::{RangeCompressor, RANGE_FREQ_MAX, RANGE_MAX, RANGE_TOP};
+use crate::{utils::end_after, ArithmeticError, Code, FreqTable, SymbolFreq, Transform};
+use std::marker::PhantomData;
+
+/// Range Decoding algorithm
+pub struct RangeDecoder<F = FreqTable, C = u8> {
+  frequencies: F,
+  /// The coded value, relative to the bottom of the interval
+  code: Code,
+  range: Code,
+  adaptive: bool,
+  started: bool,
+  ended: bool,
+  symbol: PhantomData<C>,
+}
+
+impl<F, C> From<RangeCompressor<F, C>> for RangeDecoder<F, C> {
+  fn from(value: RangeCompressor<F, C>) -> Self {
+    Self {
+      frequencies: value.frequencies,
+      code: 0,
+      range: RANGE_MAX,
+      adaptive: value.adaptive,
+      started: false,
+      ended: false,
+      symbol: PhantomData,
+    }
+  }
+}
+
+impl<F: SymbolFreq<C>, C: Copy> RangeDecoder<F, C> {
+  /// Shifts the next byte into `self.code`
+  fn shift_code(&mut self, iter: &mut impl Iterator<Item = u8>) -> Result<(), ArithmeticError> {
+    let byte = iter.next().ok_or(ArithmeticError::Truncated)?;
+    self.code = ((self.code << 8) | Code::from(byte)) & RANGE_MAX;
+    Ok(())
+  }
+
+  /// Decodes the next character, or `None` at the end marker
+  fn decode(&mut self, iter: &mut impl Iterator<Item = u8>) -> Result<Option<C>, ArithmeticError> {
+    if !self.started {
+      self.started = true;
+      for _ in 0..4 {
+        self.shift_code(iter)?;
+      }
+    }
+
+    let len = self.frequencies.len();
+    if (len == 0) | (len > RANGE_FREQ_MAX) {
+      return Err(ArithmeticError::InvalidModel);
+    }
+    let r = self.range / len;
+    let i = self.code / r;
+
+    // stop at the end marker rather than decoding the bytes that follow it
+    let (low, high) = self.frequencies.end();
+    if (low <= i) & (i < high) {
+      return Ok(None);
+    }
+
+    let ((l, h), c) = (self.frequencies.lookup(i)).ok_or(ArithmeticError::OutOfRange)?;
+    if !((l <= i) & (i < h) & (h <= len)) {
+      return Err(ArithmeticError::OutOfRange);
+    }
+
+    // `l <= i < h` keeps the code within the narrowed range
+    self.code -= r * l;
+    self.range = r * (h - l);
+    while self.range < RANGE_TOP {
+      self.range <<= 8;
+      self.shift_code(iter)?;
+    }
+
+    // mirror the encoder's update after coding `c`
+    if self.adaptive {
+      self.frequencies.increment(c);
+    }
+    Ok(Some(c))
+  }
+}
+
+impl<F: SymbolFreq<C>, C: Copy> Transform<u8> for RangeDecoder<F, C> {
+  type Out = Result<C, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    if self.ended {
+      return None;
+    }
+
+    let decoded = self.decode(iter);
+    end_after(&mut self.ended, decoded)
+  }
+}
@@ -0,0 +1,128 @@
+use super::{RangeCompressor, RANGE_FREQ_MAX, RANGE_MAX, RANGE_TOP};
+use crate::{ArithmeticError, Code, FreqTable, SymbolFreq, Transform};
+use std::{collections::VecDeque, marker::PhantomData};
+
+/// Range Encoding algorithm
+pub struct RangeEncoder<F = FreqTable, C = u8> {
+  frequencies: F,
+  /// The bottom of the interval, with a carry in bit 32
+  low: Code,
+  range: Code,
+  /// The last byte shifted out of `low`, still open to a carry
+  cache: u8,
+  /// The number of bytes held back: `cache` followed by `0xFF`s
+  cache_size: usize,
+  /// Bytes no carry can reach, ready to be output
+  output: VecDeque<u8>,
+  adaptive: bool,
+  ended: bool,
+  failed: bool,
+  symbol: PhantomData<C>,
+}
+
+impl<F, C> From<RangeCompressor<F, C>> for RangeEncoder<F, C> {
+  fn from(value: RangeCompressor<F, C>) -> Self {
+    Self {
+      frequencies: value.frequencies,
+      low: 0,
+      range: RANGE_MAX,
+      cache: 0,
+      cache_size: 0,
+      output: VecDeque::new(),
+      adaptive: value.adaptive,
+      ended: false,
+      failed: false,
+      symbol: PhantomData,
+    }
+  }
+}
+
+impl<F, C> RangeEncoder<F, C> {
+  /// Shifts the top byte out of `low`.<br>
+  /// A run of `0xFF` bytes could still be carried into,<br>
+  /// so they're only output once the next byte rules that out.
+  fn shift_low(&mut self) {
+    let settled = (self.low < 0xFF00_0000) | (self.low > RANGE_MAX);
+    if settled | (self.cache_size == 0) {
+      let carry = (self.low >> 32) as u8;
+      let mut byte = self.cache;
+      for _ in 0..self.cache_size {
+        self.output.push_back(byte.wrapping_add(carry));
+        byte = 0xFF;
+      }
+      self.cache_size = 0;
+      self.cache = (self.low >> 24) as u8;
+    }
+    self.cache_size += 1;
+    self.low = (self.low & 0x00FF_FFFF) << 8;
+  }
+
+  /// Narrows the interval to the frequency range `(l, h)` out of `len`,<br>
+  /// shifting out bytes until the range is wide enough for the next symbol.
+  fn encode(&mut self, (l, h): (Code, Code), len: Code) -> Result<(), ArithmeticError> {
+    if l >= h {
+      return Err(ArithmeticError::ZeroFrequency);
+    }
+    if (h > len) | (len > RANGE_FREQ_MAX) {
+      return Err(ArithmeticError::InvalidModel);
+    }
+
+    let r = self.range / len;
+    self.low += r * l;
+    self.range = r * (h - l);
+    while self.range < RANGE_TOP {
+      self.range <<= 8;
+      self.shift_low();
+    }
+    Ok(())
+  }
+
+  /// Shifts every byte of `low` out, along with any held back
+  fn flush(&mut self) {
+    for _ in 0..5 {
+      self.shift_low();
+    }
+  }
+}
+
+impl<F: SymbolFreq<C>, C: Copy> Transform<C> for RangeEncoder<F, C> {
+  type Out = Result<u8, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = C>) -> Option<Self::Out> {
+    if self.failed {
+      return None;
+    }
+
+    loop {
+      if let Some(byte) = self.output.pop_front() {
+        return Some(Ok(byte));
+      }
+
+      if self.ended {
+        return None;
+      }
+
+      // get next character or mark the end of the stream,
+      // so the decoder knows exactly where to stop
+      let next = iter.next();
+      let range = match next {
+        Some(c) => self.frequencies.range(c),
+        None => self.frequencies.end(),
+      };
+
+      if let Err(e) = self.encode(range, self.frequencies.len()) {
+        self.failed = true;
+        return Some(Err(e));
+      }
+
+      match next {
+        // the decoder makes the same update after decoding `c`
+        Some(c) if self.adaptive => self.frequencies.increment(c),
+        Some(_) => {}
+        None => {
+          self.flush();
+          self.ended = true;
+        }
+      }
+    }
+  }
+}
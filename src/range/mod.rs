@@ -0,0 +1,73 @@
+use crate::{ArithmeticError, Code, Compressor, FreqTable, SymbolFreq};
+use std::marker::PhantomData;
+
+mod encoder;
+pub use encoder::RangeEncoder;
+mod decoder;
+pub use decoder::RangeDecoder;
+
+/// Bytes are output once the range drops below this
+const RANGE_TOP: Code = 1 << 24;
+/// The initial range, spanning every 32 bit code value
+const RANGE_MAX: Code = u32::MAX as Code;
+/// The largest total frequency that keeps every symbol's range non-empty
+const RANGE_FREQ_MAX: Code = 1 << 16;
+
+/// A range coder, producing bytes rather than bits.<br>
+/// This is arithmetic coding with the interval renormalised a byte at a time,<br>
+/// with carries propagated into bytes already produced as in LZMA's range coder.
+///
+/// Takes the same `SymbolFreq` models as `ArithmeticCompressor`,<br>
+/// as long as their total frequency stays within `1 << 16`.
+#[derive(Clone, Copy)]
+pub struct RangeCompressor<F = FreqTable, C = u8> {
+  frequencies: F,
+  adaptive: bool,
+  symbol: PhantomData<C>,
+}
+
+impl Default for RangeCompressor {
+  fn default() -> Self {
+    Self::new(FreqTable::default())
+  }
+}
+
+impl RangeCompressor {
+  /// A range compressor that adapts to the data it codes,<br>
+  /// see `ArithmeticCompressor::adaptive`.
+  pub fn adaptive() -> Self {
+    Self::new_adaptive(FreqTable::default())
+  }
+}
+
+impl<F, C> RangeCompressor<F, C> {
+  /// A range compressor that codes with a fixed model.
+  pub fn new(frequencies: F) -> Self {
+    Self {
+      frequencies,
+      adaptive: false,
+      symbol: PhantomData,
+    }
+  }
+
+  /// A range compressor that starts from the model `frequencies`<br>
+  /// and calls `SymbolFreq::increment` after coding each symbol.
+  pub fn new_adaptive(frequencies: F) -> Self {
+    Self {
+      adaptive: true,
+      ..Self::new(frequencies)
+    }
+  }
+}
+
+unsafe impl<F: SymbolFreq<C>, C: Copy> Compressor for RangeCompressor<F, C> {
+  type Error = ArithmeticError;
+  type Item = C;
+  type Data = u8;
+
+  type Encoder = RangeEncoder<F, C>;
+  type Decoder = RangeDecoder<F, C>;
+}
+
+#[cfg(test)]
+mod test;
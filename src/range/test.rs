@@ -0,0 +1,79 @@
+use crate::{
+  test::CompressorTests, ArithmeticCompressor, ArithmeticError, Compressor, FenwickTable,
+  FreqTable, IteratorTransforms,
+};
+use proptest::proptest;
+
+use super::RangeCompressor;
+
+proptest! {
+  #[test]
+  fn test_encode_decode(items: Vec<u8>) {
+    RangeCompressor::default().test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_adaptive_encode_decode(items: Vec<u8>) {
+    RangeCompressor::adaptive().test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_fenwick_encode_decode(items: Vec<u16>) {
+    let items = items.into_iter().map(|item| item % 1024).collect();
+    RangeCompressor::<_, u16>::new_adaptive(FenwickTable::new(1024))
+      .test_encode_decode(items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_skewed_encode_decode(items: Vec<bool>) {
+    // long runs of the likely symbol are what force carries
+    let items: Vec<u8> = items.into_iter().map(|item| item as u8 * 255).collect();
+    let mut counts = [1; 256];
+    counts[0] = 30000;
+    RangeCompressor::new(FreqTable::from_counts(counts))
+      .test_encode_decode(items)
+      .unwrap()
+  }
+}
+
+#[test]
+fn test_matches_arithmetic_ratio() {
+  let text = "the quick brown fox jumps over the lazy dog. ".repeat(256);
+  let bits = (text.bytes())
+    .apply(ArithmeticCompressor::adaptive().encoder())
+    .count();
+  let bytes = (text.bytes())
+    .apply(RangeCompressor::adaptive().encoder())
+    .count();
+  assert!(bytes * 8 < bits + 64, "{bytes} bytes vs {bits} bits");
+}
+
+#[test]
+fn test_truncated() {
+  let compressor = RangeCompressor::adaptive();
+  compressor.test_truncated(Vec::new(), 0, ArithmeticError::Truncated);
+  compressor.test_truncated((0..=255).collect(), 64, ArithmeticError::Truncated);
+}
+
+#[test]
+fn test_oversized_model() {
+  let table = FreqTable::from_counts([1 << 8; 256]);
+  let encoded = [0]
+    .into_iter()
+    .apply(RangeCompressor::new(table).encoder())
+    .collect::<Result<Vec<_>, _>>();
+  assert_eq!(encoded, Err(ArithmeticError::InvalidModel));
+}
+
+proptest! {
+  #[test]
+  fn test_decode_arbitrary(bytes: Vec<u8>) {
+    RangeCompressor::default().test_decode_arbitrary(bytes)
+  }
+
+  #[test]
+  fn test_adaptive_decode_arbitrary(bytes: Vec<u8>) {
+    RangeCompressor::adaptive().test_decode_arbitrary(bytes)
+  }
+}
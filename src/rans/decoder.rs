@@ -0,0 +1,90 @@
+use super::{RansCompressor, RansTable, RANS_BITS, RANS_L, RANS_M};
+use crate::{
+  utils::{end_after, read_varint},
+  ArithmeticError, Code, Transform,
+};
+
+/// rANS Decoding algorithm
+pub struct RansDecoder {
+  table: RansTable,
+  state: Code,
+  /// Items left to decode in the current block
+  remaining: usize,
+  ended: bool,
+}
+
+impl From<RansCompressor> for RansDecoder {
+  fn from(value: RansCompressor) -> Self {
+    Self {
+      table: value.table,
+      state: RANS_L,
+      remaining: 0,
+      ended: false,
+    }
+  }
+}
+
+impl RansDecoder {
+  /// Shifts the next byte into the state
+  fn shift_state(&mut self, iter: &mut impl Iterator<Item = u8>) -> Result<(), ArithmeticError> {
+    let byte = iter.next().ok_or(ArithmeticError::Truncated)?;
+    self.state = (self.state << 8) | Code::from(byte);
+    Ok(())
+  }
+
+  /// Reads the next block's length and initial state.<br>
+  /// Returns `false` at the empty block marking the end of the stream.
+  fn start_block(&mut self, iter: &mut impl Iterator<Item = u8>) -> Result<bool, ArithmeticError> {
+    // decoding a block in full always returns the state to where encoding began
+    if self.state != RANS_L {
+      return Err(ArithmeticError::OutOfRange);
+    }
+
+    self.remaining = read_varint(
+      iter,
+      ArithmeticError::Truncated,
+      ArithmeticError::InvalidHeader,
+    )?;
+    if self.remaining == 0 {
+      return Ok(false);
+    }
+
+    self.state = 0;
+    for _ in 0..4 {
+      self.shift_state(iter)?;
+    }
+    if !(RANS_L..RANS_L << 8).contains(&self.state) {
+      return Err(ArithmeticError::OutOfRange);
+    }
+    Ok(true)
+  }
+
+  /// Decodes the next byte, or `None` after the last block
+  fn decode(&mut self, iter: &mut impl Iterator<Item = u8>) -> Result<Option<u8>, ArithmeticError> {
+    if (self.remaining == 0) && !self.start_block(iter)? {
+      return Ok(None);
+    }
+
+    let slot = self.state % RANS_M;
+    let ((start, end), byte) = self.table.lookup(slot);
+    self.state = (end - start) * (self.state >> RANS_BITS) + slot - start;
+    while self.state < RANS_L {
+      self.shift_state(iter)?;
+    }
+
+    self.remaining -= 1;
+    Ok(Some(byte))
+  }
+}
+
+impl Transform<u8> for RansDecoder {
+  type Out = Result<u8, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    if self.ended {
+      return None;
+    }
+
+    let decoded = self.decode(iter);
+    end_after(&mut self.ended, decoded)
+  }
+}
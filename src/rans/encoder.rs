@@ -0,0 +1,88 @@
+use super::{RansCompressor, RansTable, RANS_BITS, RANS_L};
+use crate::{utils::write_varint, ArithmeticError, Transform};
+use std::collections::VecDeque;
+
+/// rANS Encoding algorithm
+pub struct RansEncoder {
+  table: RansTable,
+  block_size: usize,
+  /// Coded bytes of the current block, ready to be output
+  output: VecDeque<u8>,
+  ended: bool,
+  failed: bool,
+}
+
+impl From<RansCompressor> for RansEncoder {
+  fn from(value: RansCompressor) -> Self {
+    Self {
+      table: value.table,
+      block_size: value.block_size,
+      output: VecDeque::new(),
+      ended: false,
+      failed: false,
+    }
+  }
+}
+
+impl RansEncoder {
+  /// Encodes `block` in reverse, so that it decodes forwards.<br>
+  /// Bytes are pushed onto `stack` in the reverse of the order they're read.
+  fn encode(&self, block: &[u8], stack: &mut Vec<u8>) -> Result<(), ArithmeticError> {
+    let mut state = RANS_L;
+    for &byte in block.iter().rev() {
+      let (start, end) = self.table.range(byte);
+      let freq = end - start;
+      if freq == 0 {
+        return Err(ArithmeticError::ZeroFrequency);
+      }
+
+      // shift bytes out until encoding keeps the state below `RANS_L << 8`
+      let state_max = ((RANS_L >> RANS_BITS) << 8) * freq;
+      while state >= state_max {
+        stack.push(state as u8);
+        state >>= 8;
+      }
+      state = ((state / freq) << RANS_BITS) + (state % freq) + start;
+    }
+
+    for _ in 0..4 {
+      stack.push(state as u8);
+      state >>= 8;
+    }
+    Ok(())
+  }
+}
+
+impl Transform<u8> for RansEncoder {
+  type Out = Result<u8, ArithmeticError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    if self.failed {
+      return None;
+    }
+
+    loop {
+      if let Some(byte) = self.output.pop_front() {
+        return Some(Ok(byte));
+      }
+
+      if self.ended {
+        return None;
+      }
+
+      // an empty block marks the end of the stream
+      let block: Vec<_> = iter.by_ref().take(self.block_size).collect();
+      write_varint(block.len(), &mut self.output);
+      if block.is_empty() {
+        self.ended = true;
+        continue;
+      }
+
+      let mut stack = Vec::new();
+      if let Err(e) = self.encode(&block, &mut stack) {
+        self.failed = true;
+        return Some(Err(e));
+      }
+      self.output.extend(stack.into_iter().rev());
+    }
+  }
+}
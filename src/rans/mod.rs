@@ -0,0 +1,70 @@
+use crate::{ArithmeticError, Code, Compressor, FreqTable};
+
+mod table;
+use table::RansTable;
+mod encoder;
+pub use encoder::RansEncoder;
+mod decoder;
+pub use decoder::RansDecoder;
+
+/// The number of bits frequencies are quantized to
+const RANS_BITS: u32 = 12;
+/// The total of the quantized frequencies
+const RANS_M: Code = 1 << RANS_BITS;
+/// The bottom of the state's normalised interval `[RANS_L, RANS_L << 8)`
+const RANS_L: Code = 1 << 23;
+
+/// Range asymmetric numeral systems, coding bytes into bytes.<br>
+/// See Duda's [paper](https://arxiv.org/abs/1311.2540), this follows the byte-wise<br>
+/// renormalisation of ryg's [rans_byte](https://github.com/rygorous/ryg_rans).
+///
+/// The model's frequencies are quantized to sum to `1 << 12`.<br>
+/// rANS decodes in the reverse order it encodes, so the encoder buffers<br>
+/// blocks of items, each output with its length and the final state.
+#[derive(Clone)]
+pub struct RansCompressor {
+  table: RansTable,
+  block_size: usize,
+}
+
+impl Default for RansCompressor {
+  fn default() -> Self {
+    Self::new(FreqTable::default())
+  }
+}
+
+impl RansCompressor {
+  /// A rANS compressor coding with a quantized copy of `frequencies`.<br>
+  /// Bytes with a frequency of zero can't be encoded.
+  pub fn new(frequencies: FreqTable) -> Self {
+    Self {
+      table: RansTable::new(&frequencies),
+      block_size: 1 << 16,
+    }
+  }
+
+  /// A rANS compressor coding with the byte frequencies of `items`
+  pub fn from_sample(items: impl IntoIterator<Item = u8>) -> Self {
+    Self::new(FreqTable::from_sample(items))
+  }
+
+  /// Sets the most bytes coded together, `1 << 16` by default.<br>
+  /// Each block is held and coded in reverse, then written out after its<br>
+  /// varint length and the 4 byte state decoding starts from.
+  pub fn with_block_size(self, block_size: usize) -> Self {
+    assert!(block_size > 0, "blocks must hold at least one item");
+    Self { block_size, ..self }
+  }
+}
+
+unsafe impl Compressor for RansCompressor {
+  type Error = ArithmeticError;
+  type Item = u8;
+  type Data = u8;
+
+  type Encoder = RansEncoder;
+  type Decoder = RansDecoder;
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,41 @@
+use super::{RANS_BITS, RANS_M};
+use crate::{Code, FreqTable};
+
+/// Byte frequencies quantized to sum to `RANS_M`,<br>
+/// along with the byte for each slot to make decoding a lookup.
+#[derive(Clone)]
+pub(crate) struct RansTable {
+  cumulative: [Code; 257],
+  slots: Box<[u8; RANS_M as usize]>,
+}
+
+impl RansTable {
+  /// Quantizes the byte frequencies of `table`, ignoring its end marker.<br>
+  /// Bytes keep a non-zero frequency if they had one.
+  pub fn new(table: &FreqTable) -> Self {
+    let freqs = table.quantized(RANS_BITS);
+
+    let mut cumulative = [0; 257];
+    let mut slots = Box::new([0; RANS_M as usize]);
+    for (byte, freq) in freqs.into_iter().enumerate() {
+      let start = cumulative[byte];
+      cumulative[byte + 1] = start + freq;
+      slots[start as usize..(start + freq) as usize].fill(byte as u8);
+    }
+    Self { cumulative, slots }
+  }
+
+  /// The quantized frequency range of `byte`
+  #[inline]
+  pub fn range(&self, byte: u8) -> (Code, Code) {
+    let byte = byte as usize;
+    (self.cumulative[byte], self.cumulative[byte + 1])
+  }
+
+  /// The byte whose quantized range contains `slot`, and that range
+  #[inline]
+  pub fn lookup(&self, slot: Code) -> ((Code, Code), u8) {
+    let byte = self.slots[(slot % RANS_M) as usize];
+    (self.range(byte), byte)
+  }
+}
@@ -0,0 +1,63 @@
+use crate::{
+  test::{skewed_bytes, CompressorTests},
+  ArithmeticError, FreqTable,
+};
+use proptest::proptest;
+
+use super::RansCompressor;
+
+proptest! {
+  #[test]
+  fn test_encode_decode(items: Vec<u8>) {
+    RansCompressor::default().test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_from_sample_encode_decode(items: Vec<u8>) {
+    RansCompressor::from_sample(items.clone())
+      .test_encode_decode(items)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_small_blocks_encode_decode(items: Vec<u8>, block_size in 1..64usize) {
+    RansCompressor::from_sample(items.clone())
+      .with_block_size(block_size)
+      .test_encode_decode(items)
+      .unwrap()
+  }
+}
+
+#[test]
+fn test_from_sample_compresses_skewed() {
+  RansCompressor::from_sample(skewed_bytes()).test_compresses_skewed(RansCompressor::default());
+}
+
+#[test]
+fn test_zero_frequency_item() {
+  let mut counts = [1; 256];
+  counts[1] = 0;
+  RansCompressor::new(FreqTable::from_counts(counts))
+    .test_encode_error(vec![0, 1], ArithmeticError::ZeroFrequency);
+}
+
+#[test]
+fn test_truncated() {
+  let compressor = RansCompressor::default();
+  (compressor.clone()).test_truncated(Vec::new(), 0, ArithmeticError::Truncated);
+  compressor.test_truncated((0..=255).collect(), 64, ArithmeticError::Truncated);
+}
+
+proptest! {
+  #[test]
+  fn test_decode_arbitrary(bytes: Vec<u8>) {
+    RansCompressor::default().test_decode_arbitrary(bytes)
+  }
+
+  #[test]
+  fn test_small_blocks_decode_arbitrary(bytes: Vec<u8>) {
+    RansCompressor::default()
+      .with_block_size(3)
+      .test_decode_arbitrary(bytes)
+  }
+}
@@ -0,0 +1,100 @@
+use super::RleError;
+use crate::{
+  arithmetic::{try_read_gamma, write_gamma},
+  utils::{read_varint, write_varint},
+  Code, FromBits, IntoBits,
+};
+
+/// The longest run written by the variable width encodings,<br>
+/// so corrupt counts can't expand into endless output.
+pub const MAX_RUN: usize = 1 << 16;
+
+/// How the length of each run is written
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RunCount {
+  /// In a fixed number of bits, between `1` and `16`,<br>
+  /// splitting runs too long to fit
+  Fixed(u32),
+  /// As an Elias gamma code, so short runs cost fewer bits
+  #[default]
+  Gamma,
+  /// As a varint of 7 bit groups, least significant first, each in<br>
+  /// a byte with its top bit set if more follow, as LEB128 does
+  Varint,
+  /// In bijective base 2, least significant digit first, like bzip2's<br>
+  /// RUNA and RUNB symbols. Every digit is preceded by a one, and the<br>
+  /// last is followed by a zero.
+  RunaRunb,
+}
+
+impl RunCount {
+  /// The largest count that can be written
+  pub(crate) fn max(self) -> usize {
+    match self {
+      Self::Fixed(width) => (1 << width) - 1,
+      Self::Gamma | Self::Varint | Self::RunaRunb => MAX_RUN,
+    }
+  }
+
+  /// Writes `count`, which must be at most `max`
+  pub(crate) fn write(self, count: usize, bits: &mut Vec<bool>) {
+    match self {
+      Self::Fixed(width) => {
+        let mut into = IntoBits::default();
+        into.push(count as u32, width);
+        bits.extend(std::iter::from_fn(|| into.next_bit()));
+      }
+      Self::Gamma => write_gamma(count as Code + 1, bits),
+      Self::Varint => {
+        let mut bytes = Vec::new();
+        write_varint(count, &mut bytes);
+        let mut into = IntoBits::default();
+        for byte in bytes {
+          into.push(byte, 8);
+          bits.extend(std::iter::from_fn(|| into.next_bit()));
+        }
+      }
+      Self::RunaRunb => {
+        let mut count = count + 1;
+        while count > 0 {
+          // RUNA is worth one of the digit's place, RUNB two
+          let runb = count.is_multiple_of(2);
+          bits.extend([true, runb]);
+          count = (count - 1 - usize::from(runb)) / 2;
+        }
+        bits.push(false);
+      }
+    }
+  }
+
+  /// Reads a count written by `write`
+  pub(crate) fn read(self, iter: &mut impl Iterator<Item = bool>) -> Result<usize, RleError> {
+    let count = match self {
+      Self::Fixed(width) => FromBits::<u32>::read(width, iter).ok_or(RleError::Truncated)? as usize,
+      Self::Gamma => {
+        let value = try_read_gamma(iter, RleError::Truncated, RleError::InvalidCount)?;
+        usize::try_from(value - 1).map_err(|_| RleError::InvalidCount)?
+      }
+      Self::Varint => {
+        let mut bytes = std::iter::from_fn(|| FromBits::<u8>::read(8, iter));
+        read_varint(&mut bytes, RleError::Truncated, RleError::InvalidCount)?
+      }
+      Self::RunaRunb => {
+        let (mut count, mut place) = (0, 1);
+        while iter.next().ok_or(RleError::Truncated)? {
+          let runb = iter.next().ok_or(RleError::Truncated)?;
+          count += place * (1 + usize::from(runb));
+          place *= 2;
+          if count > MAX_RUN + 1 {
+            return Err(RleError::InvalidCount);
+          }
+        }
+        count.checked_sub(1).ok_or(RleError::InvalidCount)?
+      }
+    };
+    match count <= self.max() {
+      true => Ok(count),
+      false => Err(RleError::InvalidCount),
+    }
+  }
+}
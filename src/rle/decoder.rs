@@ -0,0 +1,104 @@
+use std::iter;
+
+use super::{BitRleCompressor, RleCompressor, RleError, RunCount};
+use crate::{utils::end_after, FromBits, Transform};
+
+/// Byte run-length decoding algorithm
+pub struct RleDecoder {
+  count: RunCount,
+  /// The byte of the current run, and how many are left
+  run: (u8, usize),
+  ended: bool,
+}
+
+impl From<RleCompressor> for RleDecoder {
+  fn from(value: RleCompressor) -> Self {
+    Self {
+      count: value.count,
+      run: (0, 0),
+      ended: false,
+    }
+  }
+}
+
+impl RleDecoder {
+  /// Decodes the next run, or `None` if there are no more
+  fn decode(&mut self, iter: &mut impl Iterator<Item = bool>) -> Result<Option<()>, RleError> {
+    let Some(first) = iter.next() else {
+      return Ok(None);
+    };
+    let mut iter = iter::once(first).chain(iter);
+    let value = FromBits::<u8>::read(u8::BITS, &mut iter).ok_or(RleError::Truncated)?;
+    self.run = (value, self.count.read(&mut iter)? + 1);
+    Ok(Some(()))
+  }
+}
+
+impl Transform<bool> for RleDecoder {
+  type Out = Result<u8, RleError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = bool>) -> Option<Self::Out> {
+    if self.run.1 == 0 && !self.ended {
+      let decoded = self.decode(iter);
+      if let Some(Err(e)) = end_after(&mut self.ended, decoded) {
+        return Some(Err(e));
+      }
+    }
+    if self.run.1 == 0 {
+      return None;
+    }
+    self.run.1 -= 1;
+    Some(Ok(self.run.0))
+  }
+}
+
+/// Bit run-length decoding algorithm
+pub struct BitRleDecoder {
+  count: RunCount,
+  /// The value of the next run
+  value: bool,
+  /// The bit of the current run, and how many are left
+  run: (bool, usize),
+  ended: bool,
+}
+
+impl From<BitRleCompressor> for BitRleDecoder {
+  fn from(value: BitRleCompressor) -> Self {
+    Self {
+      count: value.count,
+      value: false,
+      run: (false, 0),
+      ended: false,
+    }
+  }
+}
+
+impl BitRleDecoder {
+  /// Decodes the next run, or `None` if there are no more
+  fn decode(&mut self, iter: &mut impl Iterator<Item = bool>) -> Result<Option<()>, RleError> {
+    let Some(first) = iter.next() else {
+      return Ok(None);
+    };
+    let length = self.count.read(&mut iter::once(first).chain(iter))?;
+    self.run = (self.value, length);
+    self.value = !self.value;
+    Ok(Some(()))
+  }
+}
+
+impl Transform<bool> for BitRleDecoder {
+  type Out = Result<bool, RleError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = bool>) -> Option<Self::Out> {
+    // empty runs just switch values
+    while self.run.1 == 0 && !self.ended {
+      let decoded = self.decode(iter);
+      if let Some(Err(e)) = end_after(&mut self.ended, decoded) {
+        return Some(Err(e));
+      }
+    }
+    if self.run.1 == 0 {
+      return None;
+    }
+    self.run.1 -= 1;
+    Some(Ok(self.run.0))
+  }
+}
@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+
+use super::{BitRleCompressor, RleCompressor, RleError, RunCount};
+use crate::{IntoBits, Transform};
+
+/// Byte run-length encoding algorithm
+pub struct RleEncoder {
+  count: RunCount,
+  /// The byte after the last run, read to find where it ended
+  peeked: Option<u8>,
+  bits: VecDeque<bool>,
+}
+
+impl From<RleCompressor> for RleEncoder {
+  fn from(value: RleCompressor) -> Self {
+    Self {
+      count: value.count,
+      peeked: None,
+      bits: VecDeque::new(),
+    }
+  }
+}
+
+impl Transform<u8> for RleEncoder {
+  type Out = Result<bool, RleError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = u8>) -> Option<Self::Out> {
+    if self.bits.is_empty() {
+      let value = self.peeked.take().or_else(|| iter.next())?;
+      let mut length = 1;
+      while length <= self.count.max() {
+        match iter.next() {
+          Some(byte) if byte == value => length += 1,
+          byte => {
+            self.peeked = byte;
+            break;
+          }
+        }
+      }
+
+      let mut into = IntoBits::default();
+      into.push(value, u8::BITS);
+      let mut bits: Vec<_> = std::iter::from_fn(|| into.next_bit()).collect();
+      self.count.write(length - 1, &mut bits);
+      self.bits.extend(bits);
+    }
+    self.bits.pop_front().map(Ok)
+  }
+}
+
+/// Bit run-length encoding algorithm
+pub struct BitRleEncoder {
+  count: RunCount,
+  /// The value of the next run
+  value: bool,
+  /// The bit after the last run, read to find where it ended
+  peeked: Option<bool>,
+  bits: VecDeque<bool>,
+}
+
+impl From<BitRleCompressor> for BitRleEncoder {
+  fn from(value: BitRleCompressor) -> Self {
+    Self {
+      count: value.count,
+      value: false,
+      peeked: None,
+      bits: VecDeque::new(),
+    }
+  }
+}
+
+impl Transform<bool> for BitRleEncoder {
+  type Out = Result<bool, RleError>;
+  fn next(&mut self, iter: &mut impl Iterator<Item = bool>) -> Option<Self::Out> {
+    // empty runs are only written before more bits, to switch values
+    while self.bits.is_empty() {
+      self.peeked = Some(self.peeked.take().or_else(|| iter.next())?);
+      let mut length = 0;
+      while length < self.count.max() && self.peeked == Some(self.value) {
+        length += 1;
+        self.peeked = iter.next();
+      }
+
+      let mut bits = Vec::new();
+      self.count.write(length, &mut bits);
+      self.bits.extend(bits);
+      self.value = !self.value;
+    }
+    self.bits.pop_front().map(Ok)
+  }
+}
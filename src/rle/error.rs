@@ -0,0 +1,21 @@
+use std::{error::Error, fmt};
+
+/// Errors produced when decoding runs fails
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RleError {
+  /// The bits ran out partway through a run
+  Truncated,
+  /// A run was longer than the encoder ever writes
+  InvalidCount,
+}
+
+impl fmt::Display for RleError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Truncated => write!(f, "stream ended partway through a run"),
+      Self::InvalidCount => write!(f, "run is longer than the encoding allows"),
+    }
+  }
+}
+
+impl Error for RleError {}
@@ -0,0 +1,76 @@
+use crate::Compressor;
+
+mod count;
+pub use count::{RunCount, MAX_RUN};
+mod error;
+pub use error::RleError;
+mod encoder;
+pub use encoder::{BitRleEncoder, RleEncoder};
+mod decoder;
+pub use decoder::{BitRleDecoder, RleDecoder};
+
+/// Run-length encoding of bytes.<br>
+/// Each run of a repeated byte is written as the byte's 8 bits,<br>
+/// followed by the run's length less one.
+#[derive(Clone, Copy, Default)]
+pub struct RleCompressor {
+  count: RunCount,
+}
+
+/// Run-length encoding of bits, for sparse streams.<br>
+/// Runs of `false` and `true` alternate, starting with `false`,<br>
+/// so only their lengths are written. Any run can be empty.
+#[derive(Clone, Copy, Default)]
+pub struct BitRleCompressor {
+  count: RunCount,
+}
+
+/// Checks a fixed width fits the counts the decoders can read
+fn check_count(count: RunCount) -> RunCount {
+  if let RunCount::Fixed(width) = count {
+    assert!(
+      (1..=16).contains(&width),
+      "fixed counts must be between 1 and 16 bits"
+    );
+  }
+  count
+}
+
+impl RleCompressor {
+  /// Sets how run lengths are written, `Gamma` by default
+  pub fn with_count(self, count: RunCount) -> Self {
+    Self {
+      count: check_count(count),
+    }
+  }
+}
+
+impl BitRleCompressor {
+  /// Sets how run lengths are written, `Gamma` by default
+  pub fn with_count(self, count: RunCount) -> Self {
+    Self {
+      count: check_count(count),
+    }
+  }
+}
+
+unsafe impl Compressor for RleCompressor {
+  type Error = RleError;
+  type Item = u8;
+  type Data = bool;
+
+  type Encoder = RleEncoder;
+  type Decoder = RleDecoder;
+}
+
+unsafe impl Compressor for BitRleCompressor {
+  type Error = RleError;
+  type Item = bool;
+  type Data = bool;
+
+  type Encoder = BitRleEncoder;
+  type Decoder = BitRleDecoder;
+}
+
+#[cfg(test)]
+mod test;
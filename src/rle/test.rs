@@ -0,0 +1,162 @@
+use crate::{test::CompressorTests, ArithmeticCompressor, Compressor, IteratorTransforms};
+use proptest::{
+  collection::vec,
+  prop_oneof, proptest,
+  strategy::{Just, Strategy},
+};
+
+use super::{BitRleCompressor, RleCompressor, RleError, RunCount};
+
+/// Every count encoding, with narrow fixed widths so runs get split
+fn counts() -> impl Strategy<Value = RunCount> {
+  prop_oneof![
+    (1..5u32).prop_map(RunCount::Fixed),
+    Just(RunCount::Gamma),
+    Just(RunCount::Varint),
+    Just(RunCount::RunaRunb),
+  ]
+}
+
+/// Bytes with long runs of few values
+fn runs() -> impl Strategy<Value = Vec<u8>> {
+  vec((0..3u8, 1..40usize), 0..32).prop_map(|runs| {
+    (runs.into_iter())
+      .flat_map(|(value, length)| vec![value; length])
+      .collect()
+  })
+}
+
+proptest! {
+  #[test]
+  fn test_encode_decode(items: Vec<u8>, count in counts()) {
+    RleCompressor::default().with_count(count).test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_runs_encode_decode(items in runs(), count in counts()) {
+    RleCompressor::default().with_count(count).test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_bits_encode_decode(items: Vec<bool>, count in counts()) {
+    BitRleCompressor::default().with_count(count).test_encode_decode(items).unwrap()
+  }
+
+  #[test]
+  fn test_bit_runs_encode_decode(items in runs(), count in counts()) {
+    let bits = items.into_iter().map(|byte| byte == 0).collect();
+    BitRleCompressor::default().with_count(count).test_encode_decode(bits).unwrap()
+  }
+
+  #[test]
+  fn test_runa_runb_counts(count in 0..super::MAX_RUN) {
+    let mut bits = Vec::new();
+    RunCount::RunaRunb.write(count, &mut bits);
+    assert_eq!(RunCount::RunaRunb.read(&mut bits.into_iter()), Ok(count));
+  }
+
+  #[test]
+  fn test_varint_counts(count in 0..super::MAX_RUN) {
+    let mut bits = Vec::new();
+    RunCount::Varint.write(count, &mut bits);
+    assert_eq!(bits.len(), 8 * (1 + usize::from(count >= 0x80) + usize::from(count >= 0x4000)));
+    assert_eq!(RunCount::Varint.read(&mut bits.into_iter()), Ok(count));
+  }
+}
+
+#[test]
+fn test_varint_errors() {
+  let read = |bits: Vec<bool>| RunCount::Varint.read(&mut bits.into_iter());
+  assert_eq!(read(vec![true; 12]), Err(RleError::Truncated));
+  assert_eq!(read(vec![true; 8 * 12]), Err(RleError::InvalidCount));
+  // 1 << 17, longer than any run
+  let long = ([0x80u8, 0x80, 0x08].into_iter())
+    .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+    .collect();
+  assert_eq!(read(long), Err(RleError::InvalidCount));
+}
+
+#[test]
+fn test_gamma_errors() {
+  let read = |bits: Vec<bool>| RunCount::Gamma.read(&mut bits.into_iter());
+  assert_eq!(read(vec![false; 8]), Err(RleError::Truncated));
+  let too_wide = [vec![false; 80], vec![true; 80]].concat();
+  assert_eq!(read(too_wide), Err(RleError::InvalidCount));
+}
+
+#[test]
+fn test_runa_runb_digits() {
+  // 5 is RUNA RUNB, one in the first place and two in the second
+  let mut bits = Vec::new();
+  RunCount::RunaRunb.write(4, &mut bits);
+  assert_eq!(bits, [true, false, true, true, false]);
+}
+
+#[test]
+fn test_sparse_bits() {
+  // a one every few hundred bits, as left by coding a skewed source
+  let items: Vec<_> = (0..1 << 14)
+    .map(|i: u32| i.wrapping_mul(2654435761).is_multiple_of(251))
+    .collect();
+  let encoded = (items.iter().copied())
+    .apply(BitRleCompressor::default().encoder())
+    .count();
+  assert!(encoded < items.len() / 8, "{encoded} bits");
+}
+
+#[test]
+fn test_after_arithmetic() {
+  // the bits coders produce can be run-length coded in turn
+  let items = [0u8; 1024].into_iter().chain([1; 8]);
+  let bits: Vec<_> = items
+    .apply(ArithmeticCompressor::adaptive().encoder())
+    .collect::<Result<_, _>>()
+    .unwrap();
+  BitRleCompressor::default()
+    .test_encode_decode(bits)
+    .unwrap();
+}
+
+#[test]
+fn test_invalid_runs() {
+  let decode = |bits: &[u8]| {
+    (bits.iter().map(|&bit| bit == 1))
+      .apply(
+        RleCompressor::default()
+          .with_count(RunCount::RunaRunb)
+          .decoder(),
+      )
+      .collect::<Result<Vec<_>, _>>()
+  };
+
+  assert_eq!(
+    decode(&[0, 1, 1, 0, 0, 0, 0, 1, 1, 0, 1, 1, 0]),
+    Ok(b"aaaaa".to_vec())
+  );
+  assert_eq!(decode(&[0, 1, 1, 0]), Err(RleError::Truncated));
+  assert_eq!(
+    decode(&[0, 1, 1, 0, 0, 0, 0, 1, 0]),
+    Err(RleError::InvalidCount)
+  );
+  assert_eq!(
+    decode(&[0, 1, 1, 0, 0, 0, 0, 1, 1, 0, 1]),
+    Err(RleError::Truncated)
+  );
+  let long = [1; 40];
+  assert_eq!(
+    decode(&[&[0, 1, 1, 0, 0, 0, 0, 1], long.as_slice()].concat()),
+    Err(RleError::InvalidCount)
+  );
+}
+
+proptest! {
+  #[test]
+  fn test_decode_arbitrary(bits: Vec<bool>, count in counts()) {
+    RleCompressor::default().with_count(count).test_decode_arbitrary(bits)
+  }
+
+  #[test]
+  fn test_bits_decode_arbitrary(bits: Vec<bool>, count in counts()) {
+    BitRleCompressor::default().with_count(count).test_decode_arbitrary(bits)
+  }
+}
@@ -0,0 +1,72 @@
+use crate::Symbol;
+
+mod sais;
+use sais::sa_is;
+
+/// The starting positions of `text`'s suffixes, in sorted order.<br>
+/// A suffix that is a prefix of another sorts first, as if `text` ended in a sentinel.
+///
+/// Built in linear time with SA-IS, see Nong, Zhang & Chan's<br>
+/// [paper](https://doi.org/10.1109/DCC.2009.42) for details.<br>
+/// Alphabets much larger than `text` are ranked down to its distinct symbols first,<br>
+/// so symbols must be ordered the same way as their indices.
+pub fn suffix_array<T: Symbol + Ord>(text: &[T]) -> Vec<usize> {
+  let Some(upper) = text.iter().map(|&symbol| symbol.index()).max() else {
+    return Vec::new();
+  };
+  if upper <= 2 * text.len() + 256 {
+    let text: Vec<_> = text.iter().map(|&symbol| symbol.index()).collect();
+    return sa_is(&text, upper);
+  }
+
+  let mut symbols = text.to_vec();
+  symbols.sort_unstable();
+  symbols.dedup();
+  let text: Vec<_> = (text.iter())
+    .map(|symbol| {
+      let Ok(rank) = symbols.binary_search(symbol) else {
+        unreachable!("every symbol in text is ranked")
+      };
+      rank
+    })
+    .collect();
+  sa_is(&text, symbols.len() - 1)
+}
+
+/// The longest common prefix of each suffix in `suffixes` with the one before it,<br>
+/// with `0` for the first. `suffixes` must be the suffix array of `text`.
+///
+/// Built in linear time, see Kasai et al's [paper](https://doi.org/10.1007/3-540-48194-X_17) for details.
+pub fn lcp_array<T: Eq>(text: &[T], suffixes: &[usize]) -> Vec<usize> {
+  assert_eq!(
+    text.len(),
+    suffixes.len(),
+    "suffixes must be a suffix array of text"
+  );
+  let mut ranks = vec![0; text.len()];
+  for (rank, &suffix) in suffixes.iter().enumerate() {
+    ranks[suffix] = rank;
+  }
+
+  // the common prefix shrinks by at most one moving to the next suffix in text order
+  let mut lcp = vec![0; text.len()];
+  let mut length = 0;
+  for (suffix, &rank) in ranks.iter().enumerate() {
+    if rank == 0 {
+      length = 0;
+      continue;
+    }
+    let previous = suffixes[rank - 1];
+    while (text.get(suffix + length))
+      .is_some_and(|symbol| Some(symbol) == text.get(previous + length))
+    {
+      length += 1;
+    }
+    lcp[rank] = length;
+    length = length.saturating_sub(1);
+  }
+  lcp
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,109 @@
+/// Marks a slot with no suffix or a position that isn't LMS
+const EMPTY: usize = usize::MAX;
+
+/// The suffix array of `text`, whose symbols are at most `upper`.
+///
+/// Suffixes are typed S or L by whether they sort before or after the next,<br>
+/// and the leftmost S suffixes (LMS) of each run are sorted first, by<br>
+/// recursing on their substrings if they aren't distinct. Every other<br>
+/// suffix is then induced from them in one pass each way over the buckets.
+pub(crate) fn sa_is(text: &[usize], upper: usize) -> Vec<usize> {
+  let n = text.len();
+  match n {
+    0 => return Vec::new(),
+    1 => return vec![0],
+    2 if text[0] < text[1] => return vec![0, 1],
+    2 => return vec![1, 0],
+    _ => (),
+  }
+
+  // the last suffix is L, sorting after the empty suffix
+  let mut small = vec![false; n];
+  for i in (0..n - 1).rev() {
+    small[i] = match text[i].cmp(&text[i + 1]) {
+      std::cmp::Ordering::Equal => small[i + 1],
+      ordering => ordering.is_lt(),
+    };
+  }
+
+  // the start of each symbol's L bucket and S bucket
+  let mut start_l = vec![0; upper + 2];
+  let mut start_s = vec![0; upper + 2];
+  for i in 0..n {
+    match small[i] {
+      false => start_s[text[i]] += 1,
+      true => start_l[text[i] + 1] += 1,
+    }
+  }
+  for symbol in 0..=upper {
+    start_s[symbol] += start_l[symbol];
+    start_l[symbol + 1] += start_s[symbol];
+  }
+
+  let is_lms = |i: usize| i > 0 && !small[i - 1] && small[i];
+  let induce = |suffixes: &mut [usize], lms: &[usize]| {
+    suffixes.fill(EMPTY);
+    let mut buckets = start_s.clone();
+    for &i in lms {
+      suffixes[buckets[text[i]]] = i;
+      buckets[text[i]] += 1;
+    }
+
+    buckets.copy_from_slice(&start_l);
+    suffixes[buckets[text[n - 1]]] = n - 1;
+    buckets[text[n - 1]] += 1;
+    for k in 0..n {
+      let i = suffixes[k];
+      if i != EMPTY && i > 0 && !small[i - 1] {
+        suffixes[buckets[text[i - 1]]] = i - 1;
+        buckets[text[i - 1]] += 1;
+      }
+    }
+
+    buckets.copy_from_slice(&start_l);
+    for k in (0..n).rev() {
+      let i = suffixes[k];
+      if i != EMPTY && i > 0 && small[i - 1] {
+        buckets[text[i - 1] + 1] -= 1;
+        suffixes[buckets[text[i - 1] + 1]] = i - 1;
+      }
+    }
+  };
+
+  let lms: Vec<_> = (1..n).filter(|&i| is_lms(i)).collect();
+  let mut lms_index = vec![EMPTY; n];
+  for (index, &i) in lms.iter().enumerate() {
+    lms_index[i] = index;
+  }
+
+  let mut suffixes = vec![EMPTY; n];
+  induce(&mut suffixes, &lms);
+  if lms.is_empty() {
+    return suffixes;
+  }
+
+  // name each LMS substring by its rank, equal substrings sharing a name
+  let sorted: Vec<_> = (suffixes.iter().copied())
+    .filter(|&i| lms_index[i] != EMPTY)
+    .collect();
+  let end = |i: usize| lms.get(lms_index[i] + 1).copied().unwrap_or(n);
+  let mut names = vec![0; lms.len()];
+  let mut name = 0;
+  for pair in sorted.windows(2) {
+    let (a, b) = (pair[0], pair[1]);
+    let same =
+      end(a) - a == end(b) - b && end(a) < n && end(b) < n && text[a..=end(a)] == text[b..=end(b)];
+    if !same {
+      name += 1;
+    }
+    names[lms_index[b]] = name;
+  }
+
+  // the LMS suffixes sort in the same order as their names' suffixes
+  let mut sorted_lms = sa_is(&names, name);
+  for i in sorted_lms.iter_mut() {
+    *i = lms[*i];
+  }
+  induce(&mut suffixes, &sorted_lms);
+  suffixes
+}
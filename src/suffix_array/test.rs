@@ -0,0 +1,85 @@
+use proptest::{collection::vec, proptest};
+
+use super::{lcp_array, suffix_array};
+
+/// Sorts the suffixes by comparing them in full
+fn naive_suffix_array<T: Ord>(text: &[T]) -> Vec<usize> {
+  let mut suffixes: Vec<_> = (0..text.len()).collect();
+  suffixes.sort_by(|&a, &b| text[a..].cmp(&text[b..]));
+  suffixes
+}
+
+/// Compares neighbouring suffixes symbol by symbol
+fn naive_lcp_array<T: Eq>(text: &[T], suffixes: &[usize]) -> Vec<usize> {
+  let common = |a: usize, b: usize| {
+    (text[a..].iter().zip(&text[b..]))
+      .take_while(|(x, y)| x == y)
+      .count()
+  };
+  (0..suffixes.len())
+    .map(|rank| match rank {
+      0 => 0,
+      _ => common(suffixes[rank - 1], suffixes[rank]),
+    })
+    .collect()
+}
+
+proptest! {
+  #[test]
+  fn test_bytes(text: Vec<u8>) {
+    assert_eq!(suffix_array(&text), naive_suffix_array(&text));
+  }
+
+  #[test]
+  fn test_small_alphabet(text in vec(0..3u8, 0..512)) {
+    // long repeats make the LMS substrings recurse several times
+    assert_eq!(suffix_array(&text), naive_suffix_array(&text));
+  }
+
+  #[test]
+  fn test_u16(text in vec(0..1000u16, 0..512)) {
+    assert_eq!(suffix_array(&text), naive_suffix_array(&text));
+  }
+
+  #[test]
+  fn test_u32(text in vec(0..4u32, 0..256), scale: u32) {
+    // spread out over the alphabet, so it has to be ranked down
+    let text: Vec<_> = text.into_iter().map(|symbol| symbol.wrapping_mul(scale | 1 << 30)).collect();
+    assert_eq!(suffix_array(&text), naive_suffix_array(&text));
+  }
+
+  #[test]
+  fn test_chars(text: String) {
+    let text: Vec<char> = text.chars().collect();
+    assert_eq!(suffix_array(&text), naive_suffix_array(&text));
+  }
+
+  #[test]
+  fn test_lcp(text in vec(0..3u8, 0..512)) {
+    let suffixes = suffix_array(&text);
+    assert_eq!(lcp_array(&text, &suffixes), naive_lcp_array(&text, &suffixes));
+  }
+}
+
+#[test]
+fn test_banana() {
+  let text = b"banana";
+  let suffixes = suffix_array(text);
+  assert_eq!(suffixes, [5, 3, 1, 0, 4, 2]);
+  assert_eq!(lcp_array(text, &suffixes), [0, 1, 3, 0, 0, 2]);
+}
+
+#[test]
+fn test_long_repeats() {
+  // quadratic to compare in full, but linear for SA-IS
+  let text = vec![7u8; 1 << 20];
+  let suffixes = suffix_array(&text);
+  assert!(suffixes
+    .iter()
+    .rev()
+    .enumerate()
+    .all(|(i, &suffix)| i == suffix));
+  assert!((lcp_array(&text, &suffixes).iter())
+    .enumerate()
+    .all(|(i, &lcp)| i == lcp));
+}
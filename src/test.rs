@@ -25,6 +25,94 @@ where
         assert_eq!(items, decoded);
         Ok(())
     }
+
+    /// Utility method to test decoding what `self` encodes `items` to with<br>
+    /// `decoder`, which only has the header written ahead of the data to go off.
+    fn test_header_decode_without_sample(
+        self,
+        decoder: Self,
+        items: Vec<Self::Item>,
+    ) -> Result<(), Self::Error> {
+        let encoded = items
+            .clone()
+            .into_iter()
+            .apply(self.encoder())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let decoded = encoded
+            .into_iter()
+            .apply(decoder.decoder())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(items, decoded);
+        Ok(())
+    }
+
+    /// Utility method to test decoding arbitrary, possibly corrupt, data.<br>
+    /// Decoders may return errors, but **must not** panic on any input.
+    fn test_decode_arbitrary(self, data: Vec<Self::Data>) {
+        for decoded in data.into_iter().apply(self.decoder()) {
+            if decoded.is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Utility method to test that encoding `items` fails with `error`.
+    fn test_encode_error(self, items: Vec<Self::Item>, error: Self::Error)
+    where
+        Self::Error: PartialEq + Debug,
+    {
+        let encoded = items
+            .into_iter()
+            .apply(self.encoder())
+            .collect::<Result<Vec<_>, _>>();
+        assert_eq!(encoded, Err(error));
+    }
+
+    /// Utility method to test decoding only the first `len` items of the<br>
+    /// data `items` are encoded to, which should fail with `error`.<br>
+    /// A `len` of zero tests decoding an empty stream.
+    fn test_truncated(self, items: Vec<Self::Item>, len: usize, error: Self::Error)
+    where
+        Self::Error: PartialEq + Debug,
+    {
+        let encoded = items
+            .into_iter()
+            .apply(self.clone().encoder())
+            .take(len)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let decoded = encoded
+            .into_iter()
+            .apply(self.decoder())
+            .collect::<Result<Vec<_>, _>>();
+        assert_eq!(decoded, Err(error));
+    }
+
+    /// Utility method to test that a model fitted to `skewed_bytes`<br>
+    /// codes them in under half the size the `baseline` model does.
+    fn test_compresses_skewed(self, baseline: Self)
+    where
+        Self: Compressor<Item = u8>,
+    {
+        let encoded_len = |compressor: Self| {
+            skewed_bytes()
+                .into_iter()
+                .apply(compressor.encoder())
+                .count()
+        };
+
+        let fitted = encoded_len(self);
+        let baseline = encoded_len(baseline);
+        assert!(fitted < baseline / 2, "{fitted} vs {baseline} items");
+    }
+}
+
+/// Bytes from a skewed source, for testing models fitted to a sample
+pub fn skewed_bytes() -> Vec<u8> {
+    (0..4096u32).map(|i| (i * i % 7) as u8).collect()
 }
 
 impl<C: Compressor> CompressorTests for C
@@ -17,3 +17,44 @@ impl<I> Transform<I> for ResultWrap<I> {
     Some(Ok(item))
   }
 }
+
+/// Passes on one step of decoding, where `Ok(None)` is the end of the stream,<br>
+/// setting `ended` once nothing more can be decoded after the end or an error.
+pub(crate) fn end_after<T, E>(
+  ended: &mut bool,
+  decoded: Result<Option<T>, E>,
+) -> Option<Result<T, E>> {
+  *ended = !matches!(decoded, Ok(Some(_)));
+  decoded.transpose()
+}
+
+/// Writes `value` 7 bits at a time, least significant first,<br>
+/// with the top bit of each byte set if more follow.
+pub(crate) fn write_varint(mut value: usize, bytes: &mut impl Extend<u8>) {
+  while value >= 0x80 {
+    bytes.extend([(value as u8) | 0x80]);
+    value >>= 7;
+  }
+  bytes.extend([value as u8]);
+}
+
+/// Reads a value written by `write_varint`, failing with `truncated`<br>
+/// if the bytes run out, or `invalid` if it overflows a `usize`.
+pub(crate) fn read_varint<E: Copy>(
+  iter: &mut impl Iterator<Item = u8>,
+  truncated: E,
+  invalid: E,
+) -> Result<usize, E> {
+  let mut value = 0;
+  for shift in (0..usize::BITS).step_by(7) {
+    let byte = iter.next().ok_or(truncated)?;
+    value |= ((byte & 0x7F) as usize)
+      .checked_shl(shift)
+      .filter(|bits| bits >> shift == (byte & 0x7F) as usize)
+      .ok_or(invalid)?;
+    if byte < 0x80 {
+      return Ok(value);
+    }
+  }
+  Err(invalid)
+}